@@ -0,0 +1,199 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, Lit, LitStr, Path, Variant};
+
+enum Token {
+    Char(char),
+    Str(String),
+    Parse,
+}
+
+struct Mapping<'a> {
+    variant: &'a Variant,
+    token: Token,
+}
+
+fn token(variant: &Variant) -> Result<Token, Error> {
+    let attribute = variant
+        .attrs
+        .iter()
+        .find(|attribute| attribute.path().is_ident("token"))
+        .ok_or_else(|| Error::new_spanned(variant, "missing #[token(...)] attribute"))?;
+    if let Ok(ident) = attribute.parse_args::<Ident>() {
+        return match ident.to_string().as_str() {
+            "parse" => Ok(Token::Parse),
+            _ => Err(Error::new_spanned(ident, "expected a literal or `parse`")),
+        };
+    }
+    match attribute.parse_args::<Lit>()? {
+        Lit::Char(value) => Ok(Token::Char(value.value())),
+        Lit::Str(value) => Ok(Token::Str(value.value())),
+        lit => Err(Error::new_spanned(lit, "expected a char or string literal")),
+    }
+}
+
+fn krate(input: &DeriveInput) -> Result<Path, Error> {
+    let mut krate = syn::parse_quote!(crate);
+    for attribute in input
+        .attrs
+        .iter()
+        .filter(|attribute| attribute.path().is_ident("maybe_from"))
+    {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                krate = meta.value()?.parse::<LitStr>()?.parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("expected `crate = \"...\"`"))
+            }
+        })?;
+    }
+    Ok(krate)
+}
+
+fn duplicate<'a>(mappings: &[Mapping<'a>]) -> Option<(&'a Variant, &'a Variant)> {
+    mappings.iter().enumerate().find_map(|(index, mapping)| {
+        mappings[..index]
+            .iter()
+            .find(|earlier| match (&earlier.token, &mapping.token) {
+                (Token::Char(a), Token::Char(b)) => a == b,
+                (Token::Str(a), Token::Str(b)) => a == b,
+                _ => false,
+            })
+            .map(|earlier| (earlier.variant, mapping.variant))
+    })
+}
+
+fn mappings(input: &DeriveInput) -> Result<Vec<Mapping<'_>>, Error> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                input,
+                "MaybeFrom can only be derived for enums",
+            ))
+        }
+    };
+    let mappings = data
+        .variants
+        .iter()
+        .map(|variant| {
+            let token = token(variant)?;
+            match (&token, &variant.fields) {
+                (Token::Parse, Fields::Unnamed(fields)) if fields.unnamed.len() == 1 => (),
+                (Token::Parse, _) => {
+                    return Err(Error::new_spanned(
+                        variant,
+                        "#[token(parse)] needs a variant with exactly one unnamed field",
+                    ))
+                }
+                (_, Fields::Unit) => (),
+                _ => {
+                    return Err(Error::new_spanned(
+                        variant,
+                        "token literals can only be used on unit variants",
+                    ))
+                }
+            }
+            Ok(Mapping { variant, token })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let chars = mappings
+        .iter()
+        .filter(|mapping| matches!(mapping.token, Token::Char(_)))
+        .count();
+    let parsed = mappings
+        .iter()
+        .filter(|mapping| matches!(mapping.token, Token::Parse))
+        .count();
+    if chars > 0 && chars < mappings.len() {
+        return Err(Error::new_spanned(
+            input,
+            "char tokens cannot be mixed with string tokens",
+        ));
+    }
+    if parsed > 1 {
+        return Err(Error::new_spanned(
+            input,
+            "only one variant can use #[token(parse)]",
+        ));
+    }
+    if let Some((earlier, variant)) = duplicate(&mappings) {
+        return Err(Error::new_spanned(
+            variant,
+            format!("duplicate token, already used by `{}`", earlier.ident),
+        ));
+    }
+    if mappings.is_empty() {
+        return Err(Error::new_spanned(input, "enum has no variants"));
+    }
+    Ok(mappings)
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let mappings = mappings(input)?;
+    let krate = krate(input)?;
+    let name = &input.ident;
+    let literals = mappings.iter().filter_map(|mapping| {
+        let ident = &mapping.variant.ident;
+        match &mapping.token {
+            Token::Char(value) => Some(quote! { #value => Some(#name::#ident), }),
+            Token::Str(value) => Some(quote! { #value => Some(#name::#ident), }),
+            Token::Parse => None,
+        }
+    });
+    let fallback = mappings
+        .iter()
+        .find(|mapping| matches!(mapping.token, Token::Parse))
+        .map(|mapping| {
+            let ident = &mapping.variant.ident;
+            quote! { value => value.parse().ok().map(#name::#ident), }
+        })
+        .unwrap_or_else(|| quote! { _ => None, });
+    let source = match mappings[0].token {
+        Token::Char(_) => quote! { char },
+        _ => quote! { &str },
+    };
+    let renders = mappings.iter().map(|mapping| {
+        let ident = &mapping.variant.ident;
+        match &mapping.token {
+            Token::Char(value) => quote! { #name::#ident => write!(f, "{}", #value), },
+            Token::Str(value) => quote! { #name::#ident => write!(f, "{}", #value), },
+            Token::Parse => quote! { #name::#ident(value) => write!(f, "{}", value), },
+        }
+    });
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #krate::maybe_from::MaybeFrom<#source> for #name #type_generics #where_clause {
+            fn maybe_from(value: #source) -> Option<Self> {
+                match value {
+                    #(#literals)*
+                    #fallback
+                }
+            }
+        }
+
+        impl #impl_generics std::fmt::Display for #name #type_generics #where_clause {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    #(#renders)*
+                }
+            }
+        }
+
+        impl #impl_generics #krate::render::Render for #name #type_generics #where_clause {
+            fn render(&self) -> String {
+                self.to_string()
+            }
+        }
+    })
+}
+
+#[proc_macro_derive(MaybeFrom, attributes(token, maybe_from))]
+pub fn derive_maybe_from(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
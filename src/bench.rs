@@ -0,0 +1,257 @@
+use crate::parse::ParseError;
+use crate::registry::Day;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Options {
+    pub warmup: usize,
+    pub samples: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            warmup: 1,
+            samples: 10,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Stage {
+    Parse,
+    Part(u8),
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+impl Stage {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "parse" => Some(Stage::Parse),
+            part => part.strip_prefix("part")?.parse().ok().map(Stage::Part),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        Self {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Measurement {
+    pub day: u8,
+    pub stage: Stage,
+    pub stats: Stats,
+}
+
+fn sample<F: FnMut()>(options: &Options, mut f: F) -> Stats {
+    for _ in 0..options.warmup {
+        f();
+    }
+    Stats::from_samples(
+        (0..options.samples.max(1))
+            .map(|_| {
+                let start = Instant::now();
+                f();
+                start.elapsed()
+            })
+            .collect(),
+    )
+}
+
+pub fn bench_day(
+    day: &Day,
+    input: &str,
+    options: &Options,
+) -> Result<Vec<Measurement>, ParseError> {
+    let parsed = day.solver.parse(input)?;
+    let mut measurements = vec![Measurement {
+        day: day.number,
+        stage: Stage::Parse,
+        stats: sample(options, || {
+            day.solver
+                .parse(input)
+                .expect("parse failed while benchmarking");
+        }),
+    }];
+    for part in day.parts() {
+        measurements.push(Measurement {
+            day: day.number,
+            stage: Stage::Part(part),
+            stats: sample(options, || {
+                parsed.solve(part);
+            }),
+        });
+    }
+    Ok(measurements)
+}
+
+pub struct Baseline {
+    medians: HashMap<(u8, Stage), Duration>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|error| format!("failed to read {}: {}", path.display(), error))?;
+        Self::parse(&content).map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    fn parse(content: &str) -> Result<Self, ParseError> {
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                Self::parse_entry(line)
+                    .ok_or_else(|| ParseError::new("invalid baseline entry").on_line(index + 1))
+            })
+            .collect::<Result<_, _>>()
+            .map(|medians| Self { medians })
+    }
+
+    fn parse_entry(line: &str) -> Option<((u8, Stage), Duration)> {
+        match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [day, stage, _, median, _] => Some((
+                (day.parse().ok()?, Stage::parse(stage)?),
+                Duration::from_nanos(median.parse().ok()?),
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn save(path: &Path, measurements: &[Measurement]) -> Result<(), String> {
+        fs::write(path, Self::serialize(measurements))
+            .map_err(|error| format!("failed to write {}: {}", path.display(), error))
+    }
+
+    fn serialize(measurements: &[Measurement]) -> String {
+        measurements
+            .iter()
+            .map(|m| {
+                format!(
+                    "{} {} {} {} {}\n",
+                    m.day,
+                    m.stage,
+                    m.stats.min.as_nanos(),
+                    m.stats.median.as_nanos(),
+                    m.stats.max.as_nanos()
+                )
+            })
+            .collect()
+    }
+
+    fn change(&self, measurement: &Measurement) -> Option<f64> {
+        self.medians
+            .get(&(measurement.day, measurement.stage))
+            .filter(|median| !median.is_zero())
+            .map(|median| {
+                (measurement.stats.median.as_secs_f64() / median.as_secs_f64() - 1.0) * 100.0
+            })
+    }
+}
+
+pub fn report(measurements: &[Measurement], baseline: Option<&Baseline>) -> String {
+    let mut report = format!(
+        "{:>3}  {:<6}  {:>10}  {:>10}  {:>10}",
+        "day", "stage", "min", "median", "max"
+    );
+    if baseline.is_some() {
+        report += &format!("  {:>8}", "change");
+    }
+    for m in measurements {
+        report += &format!(
+            "\n{:>3}  {:<6}  {:>10.1?}  {:>10.1?}  {:>10.1?}",
+            m.day, m.stage, m.stats.min, m.stats.median, m.stats.max
+        );
+        if let Some(baseline) = baseline {
+            report += &match baseline.change(m) {
+                Some(change) => format!("  {:>+7.1}%", change),
+                None => format!("  {:>8}", "new"),
+            };
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(day: u8, stage: Stage, millis: u64) -> Measurement {
+        Measurement {
+            day,
+            stage,
+            stats: Stats {
+                min: Duration::from_millis(millis - 1),
+                median: Duration::from_millis(millis),
+                max: Duration::from_millis(millis + 1),
+            },
+        }
+    }
+
+    #[test]
+    fn stats() {
+        let stats = Stats::from_samples(
+            [5, 1, 3, 2, 4]
+                .iter()
+                .map(|&n| Duration::from_millis(n))
+                .collect(),
+        );
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.max, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let before = [
+            measurement(3, Stage::Parse, 10),
+            measurement(3, Stage::Part(1), 20),
+        ];
+        let baseline = Baseline::parse(&Baseline::serialize(&before)).unwrap();
+        let after = [
+            measurement(3, Stage::Parse, 15),
+            measurement(3, Stage::Part(1), 10),
+            measurement(3, Stage::Part(2), 10),
+        ];
+        assert_eq!(
+            after.iter().map(|m| baseline.change(m)).collect::<Vec<_>>(),
+            vec![Some(50.0), Some(-50.0), None]
+        );
+    }
+
+    #[test]
+    fn invalid_baseline() {
+        assert_eq!(
+            Baseline::parse("3 parse 1 2 3\n3 part1 1 2\n").err(),
+            Some(ParseError::new("invalid baseline entry").on_line(2))
+        );
+    }
+}
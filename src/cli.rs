@@ -0,0 +1,506 @@
+use aoc2020::bench;
+use aoc2020::input::Source;
+use aoc2020::output::Format;
+use aoc2020::parse::Mode;
+use aoc2020::trace::{self, Level};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+pub const USAGE: &str = "usage:
+    aoc2020 list
+    aoc2020 run <day|all> [--part 1|2] [--input <path>|-] [--format text|json]
+                          [--jobs N] [--lenient] [-v|-vv|--verbosity <level>]
+                          [--trace-file <path>]
+    aoc2020 verify [<day>|all] [--answers <path>]
+    aoc2020 watch <day> [--input <path>] [--part 1|2] [--interval <ms>]
+                        [-v|-vv|--verbosity <level>] [--trace-file <path>]
+    aoc2020 new <day>
+    aoc2020 fetch <day> [--force]
+    aoc2020 submit <day> <part> [--input <path>|-] [--history <path>]
+    aoc2020 gen <day> [--size N] [--seed S]
+    aoc2020 crosscheck <day|all> [--cases N] [--size N] [--seed S]
+    aoc2020 bench <day|all> [--input <path>|-] [--warmup N] [--samples N]
+                            [--save <path>] [--baseline <path>]";
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Selection {
+    All,
+    Day(u8),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Command {
+    List,
+    Run {
+        selection: Selection,
+        part: Option<u8>,
+        input: Source,
+        format: Format,
+        jobs: usize,
+        mode: Mode,
+        trace: trace::Options,
+    },
+    Verify {
+        selection: Selection,
+        answers: Option<PathBuf>,
+    },
+    Bench {
+        selection: Selection,
+        input: Source,
+        options: bench::Options,
+        save: Option<PathBuf>,
+        baseline: Option<PathBuf>,
+    },
+    Watch {
+        day: u8,
+        part: Option<u8>,
+        input: Option<PathBuf>,
+        interval: Duration,
+        trace: trace::Options,
+    },
+    New {
+        day: u8,
+    },
+    Fetch {
+        day: u8,
+        force: bool,
+    },
+    Submit {
+        day: u8,
+        part: u8,
+        input: Source,
+        history: Option<PathBuf>,
+    },
+    Gen {
+        day: u8,
+        size: usize,
+        seed: u64,
+    },
+    Crosscheck {
+        selection: Selection,
+        cases: usize,
+        size: usize,
+        seed: u64,
+    },
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum CliError {
+    MissingCommand,
+    UnknownCommand(String),
+    MissingArgument(&'static str),
+    InvalidValue { name: &'static str, value: String },
+    UnexpectedArgument(String),
+    InputWithAll,
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use CliError::*;
+        match self {
+            MissingCommand => write!(f, "no command given"),
+            UnknownCommand(command) => write!(f, "unknown command '{}'", command),
+            MissingArgument(name) => write!(f, "missing value for {}", name),
+            InvalidValue { name, value } => write!(f, "invalid value '{}' for {}", value, name),
+            UnexpectedArgument(arg) => write!(f, "unexpected argument '{}'", arg),
+            InputWithAll => write!(f, "--input can only be used when running a single day"),
+        }
+    }
+}
+
+fn parse_selection(value: &str) -> Result<Selection, CliError> {
+    match value {
+        "all" => Ok(Selection::All),
+        day => day
+            .parse::<u8>()
+            .map(Selection::Day)
+            .map_err(|_| CliError::InvalidValue {
+                name: "<day>",
+                value: day.to_string(),
+            }),
+    }
+}
+
+fn parse_day(value: Option<String>) -> Result<u8, CliError> {
+    match parse_selection(&value.ok_or(CliError::MissingArgument("<day>"))?)? {
+        Selection::Day(day) => Ok(day),
+        Selection::All => Err(CliError::InvalidValue {
+            name: "<day>",
+            value: "all".to_string(),
+        }),
+    }
+}
+
+fn parse_part(value: Option<String>) -> Result<u8, CliError> {
+    let value = value.ok_or(CliError::MissingArgument("--part"))?;
+    match value.as_str() {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(CliError::InvalidValue {
+            name: "--part",
+            value,
+        }),
+    }
+}
+
+fn parse_format(value: Option<String>) -> Result<Format, CliError> {
+    let value = value.ok_or(CliError::MissingArgument("--format"))?;
+    match value.as_str() {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => Err(CliError::InvalidValue {
+            name: "--format",
+            value,
+        }),
+    }
+}
+
+fn parse_count<T: FromStr>(name: &'static str, value: Option<String>) -> Result<T, CliError> {
+    let value = value.ok_or(CliError::MissingArgument(name))?;
+    value
+        .parse()
+        .map_err(|_| CliError::InvalidValue { name, value })
+}
+
+fn parse_level(value: Option<String>) -> Result<Level, CliError> {
+    let value = value.ok_or(CliError::MissingArgument("--verbosity"))?;
+    Level::parse(&value).ok_or(CliError::InvalidValue {
+        name: "--verbosity",
+        value,
+    })
+}
+
+fn parse_trace<I: Iterator<Item = String>>(
+    arg: &str,
+    args: &mut I,
+    options: &mut trace::Options,
+) -> Result<bool, CliError> {
+    match arg {
+        "-v" => options.level = Level::Debug,
+        "-vv" => options.level = Level::Trace,
+        "--verbosity" => options.level = parse_level(args.next())?,
+        "--trace-file" => options.file = Some(parse_path("--trace-file", args.next())?),
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn parse_path(name: &'static str, value: Option<String>) -> Result<PathBuf, CliError> {
+    value
+        .map(PathBuf::from)
+        .ok_or(CliError::MissingArgument(name))
+}
+
+fn parse_run<I: Iterator<Item = String>>(mut args: I) -> Result<Command, CliError> {
+    let selection = parse_selection(&args.next().ok_or(CliError::MissingArgument("<day>"))?)?;
+    let mut part = None;
+    let mut input = Source::Default;
+    let mut format = Format::Text;
+    let mut jobs = 1;
+    let mut mode = Mode::Strict;
+    let mut trace = trace::Options::default();
+    while let Some(arg) = args.next() {
+        if parse_trace(&arg, &mut args, &mut trace)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--part" | "-p" => part = Some(parse_part(args.next())?),
+            "--format" | "-f" => format = parse_format(args.next())?,
+            "--jobs" | "-j" => match parse_count("--jobs", args.next())? {
+                0 => {
+                    return Err(CliError::InvalidValue {
+                        name: "--jobs",
+                        value: "0".to_string(),
+                    })
+                }
+                count => jobs = count,
+            },
+            "--input" | "-i" => {
+                input = Source::from(
+                    args.next()
+                        .ok_or(CliError::MissingArgument("--input"))?
+                        .as_str(),
+                )
+            }
+            "--lenient" => mode = Mode::Lenient,
+            _ => return Err(CliError::UnexpectedArgument(arg)),
+        }
+    }
+    if selection == Selection::All && input != Source::Default {
+        return Err(CliError::InputWithAll);
+    }
+    Ok(Command::Run {
+        selection,
+        part,
+        input,
+        format,
+        jobs,
+        mode,
+        trace,
+    })
+}
+
+fn parse_verify<I: Iterator<Item = String>>(args: I) -> Result<Command, CliError> {
+    let mut args = args.peekable();
+    let selection = match args.peek() {
+        Some(arg) if !arg.starts_with('-') => parse_selection(&args.next().unwrap())?,
+        _ => Selection::All,
+    };
+    let mut answers = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => answers = Some(parse_path("--answers", args.next())?),
+            _ => return Err(CliError::UnexpectedArgument(arg)),
+        }
+    }
+    Ok(Command::Verify { selection, answers })
+}
+
+fn parse_bench<I: Iterator<Item = String>>(mut args: I) -> Result<Command, CliError> {
+    let selection = parse_selection(&args.next().ok_or(CliError::MissingArgument("<day>"))?)?;
+    let mut input = Source::Default;
+    let mut options = bench::Options::default();
+    let mut save = None;
+    let mut baseline = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" | "-i" => {
+                input = Source::from(
+                    args.next()
+                        .ok_or(CliError::MissingArgument("--input"))?
+                        .as_str(),
+                )
+            }
+            "--warmup" => options.warmup = parse_count("--warmup", args.next())?,
+            "--samples" => options.samples = parse_count("--samples", args.next())?,
+            "--save" => save = Some(parse_path("--save", args.next())?),
+            "--baseline" => baseline = Some(parse_path("--baseline", args.next())?),
+            _ => return Err(CliError::UnexpectedArgument(arg)),
+        }
+    }
+    if selection == Selection::All && input != Source::Default {
+        return Err(CliError::InputWithAll);
+    }
+    Ok(Command::Bench {
+        selection,
+        input,
+        options,
+        save,
+        baseline,
+    })
+}
+
+fn parse_watch<I: Iterator<Item = String>>(mut args: I) -> Result<Command, CliError> {
+    let day = parse_day(args.next())?;
+    let mut part = None;
+    let mut input = None;
+    let mut interval = Duration::from_millis(500);
+    let mut trace = trace::Options::default();
+    while let Some(arg) = args.next() {
+        if parse_trace(&arg, &mut args, &mut trace)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--part" | "-p" => part = Some(parse_part(args.next())?),
+            "--input" | "-i" => input = Some(parse_path("--input", args.next())?),
+            "--interval" => {
+                interval = Duration::from_millis(parse_count("--interval", args.next())?)
+            }
+            _ => return Err(CliError::UnexpectedArgument(arg)),
+        }
+    }
+    Ok(Command::Watch {
+        day,
+        part,
+        input,
+        interval,
+        trace,
+    })
+}
+
+fn parse_fetch<I: Iterator<Item = String>>(mut args: I) -> Result<Command, CliError> {
+    let day = parse_day(args.next())?;
+    let mut force = false;
+    for arg in args {
+        match arg.as_str() {
+            "--force" => force = true,
+            _ => return Err(CliError::UnexpectedArgument(arg)),
+        }
+    }
+    Ok(Command::Fetch { day, force })
+}
+
+fn parse_gen<I: Iterator<Item = String>>(mut args: I) -> Result<Command, CliError> {
+    let day = parse_day(args.next())?;
+    let mut size = 1000;
+    let mut seed = 0;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => size = parse_count("--size", args.next())?,
+            "--seed" => seed = parse_count("--seed", args.next())?,
+            _ => return Err(CliError::UnexpectedArgument(arg)),
+        }
+    }
+    Ok(Command::Gen { day, size, seed })
+}
+
+fn parse_crosscheck<I: Iterator<Item = String>>(mut args: I) -> Result<Command, CliError> {
+    let selection = parse_selection(&args.next().ok_or(CliError::MissingArgument("<day>"))?)?;
+    let mut cases = 100;
+    let mut size = 50;
+    let mut seed = 0;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--cases" => cases = parse_count("--cases", args.next())?,
+            "--size" => size = parse_count("--size", args.next())?,
+            "--seed" => seed = parse_count("--seed", args.next())?,
+            _ => return Err(CliError::UnexpectedArgument(arg)),
+        }
+    }
+    Ok(Command::Crosscheck {
+        selection,
+        cases,
+        size,
+        seed,
+    })
+}
+
+fn parse_submit<I: Iterator<Item = String>>(mut args: I) -> Result<Command, CliError> {
+    let day = parse_day(args.next())?;
+    let part = match args.next() {
+        Some(part) if part.starts_with('-') => return Err(CliError::UnexpectedArgument(part)),
+        part => parse_part(part)?,
+    };
+    let mut input = Source::Default;
+    let mut history = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" | "-i" => {
+                input = Source::from(
+                    args.next()
+                        .ok_or(CliError::MissingArgument("--input"))?
+                        .as_str(),
+                )
+            }
+            "--history" => history = Some(parse_path("--history", args.next())?),
+            _ => return Err(CliError::UnexpectedArgument(arg)),
+        }
+    }
+    Ok(Command::Submit {
+        day,
+        part,
+        input,
+        history,
+    })
+}
+
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Command, CliError> {
+    let mut args = args.into_iter();
+    let command = args.next().ok_or(CliError::MissingCommand)?;
+    match command.as_str() {
+        "list" => match args.next() {
+            Some(arg) => Err(CliError::UnexpectedArgument(arg)),
+            None => Ok(Command::List),
+        },
+        "run" => parse_run(args),
+        "verify" => parse_verify(args),
+        "bench" => parse_bench(args),
+        "watch" => parse_watch(args),
+        "new" => {
+            let day = parse_day(args.next())?;
+            match args.next() {
+                Some(arg) => Err(CliError::UnexpectedArgument(arg)),
+                None => Ok(Command::New { day }),
+            }
+        }
+        "fetch" => parse_fetch(args),
+        "submit" => parse_submit(args),
+        "gen" => parse_gen(args),
+        "crosscheck" => parse_crosscheck(args),
+        _ => Err(CliError::UnknownCommand(command)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[parameterized(case = {
+        ("list", Command::List),
+        ("run 7", Command::Run { selection: Selection::Day(7), part: None, input: Source::Default, format: Format::Text, jobs: 1, mode: Mode::Strict, trace: trace::Options::default() }),
+        ("run all", Command::Run { selection: Selection::All, part: None, input: Source::Default, format: Format::Text, jobs: 1, mode: Mode::Strict, trace: trace::Options::default() }),
+        ("run 12 --part 2", Command::Run { selection: Selection::Day(12), part: Some(2), input: Source::Default, format: Format::Text, jobs: 1, mode: Mode::Strict, trace: trace::Options::default() }),
+        ("run all -p 1", Command::Run { selection: Selection::All, part: Some(1), input: Source::Default, format: Format::Text, jobs: 1, mode: Mode::Strict, trace: trace::Options::default() }),
+        ("run 3 --input -", Command::Run { selection: Selection::Day(3), part: None, input: Source::Stdin, format: Format::Text, jobs: 1, mode: Mode::Strict, trace: trace::Options::default() }),
+        ("run 3 -i my/input -p 2", Command::Run { selection: Selection::Day(3), part: Some(2), input: Source::File("my/input".into()), format: Format::Text, jobs: 1, mode: Mode::Strict, trace: trace::Options::default() }),
+        ("run all --format json", Command::Run { selection: Selection::All, part: None, input: Source::Default, format: Format::Json, jobs: 1, mode: Mode::Strict, trace: trace::Options::default() }),
+        ("run all --jobs 4", Command::Run { selection: Selection::All, part: None, input: Source::Default, format: Format::Text, jobs: 4, mode: Mode::Strict, trace: trace::Options::default() }),
+        ("run 8 --lenient", Command::Run { selection: Selection::Day(8), part: None, input: Source::Default, format: Format::Text, jobs: 1, mode: Mode::Lenient, trace: trace::Options::default() }),
+        ("run 17 -v", Command::Run { selection: Selection::Day(17), part: None, input: Source::Default, format: Format::Text, jobs: 1, mode: Mode::Strict, trace: trace::Options { level: Level::Debug, file: None } }),
+        ("run 8 --verbosity trace --trace-file t.log", Command::Run { selection: Selection::Day(8), part: None, input: Source::Default, format: Format::Text, jobs: 1, mode: Mode::Strict, trace: trace::Options { level: Level::Trace, file: Some("t.log".into()) } }),
+        ("watch 4 -vv", Command::Watch { day: 4, part: None, input: None, interval: Duration::from_millis(500), trace: trace::Options { level: Level::Trace, file: None } }),
+        ("verify", Command::Verify { selection: Selection::All, answers: None }),
+        ("verify 4", Command::Verify { selection: Selection::Day(4), answers: None }),
+        ("verify --answers a.txt", Command::Verify { selection: Selection::All, answers: Some("a.txt".into()) }),
+        ("bench 15", Command::Bench { selection: Selection::Day(15), input: Source::Default, options: bench::Options::default(), save: None, baseline: None }),
+        ("bench all --warmup 0 --samples 3 --save b.txt", Command::Bench { selection: Selection::All, input: Source::Default, options: bench::Options { warmup: 0, samples: 3 }, save: Some("b.txt".into()), baseline: None }),
+        ("bench 1 -i - --baseline b.txt", Command::Bench { selection: Selection::Day(1), input: Source::Stdin, options: bench::Options::default(), save: None, baseline: Some("b.txt".into()) }),
+        ("watch 3", Command::Watch { day: 3, part: None, input: None, interval: Duration::from_millis(500), trace: trace::Options::default() }),
+        ("watch 3 -i ex.txt -p 2 --interval 100", Command::Watch { day: 3, part: Some(2), input: Some("ex.txt".into()), interval: Duration::from_millis(100), trace: trace::Options::default() }),
+        ("new 19", Command::New { day: 19 }),
+        ("fetch 9", Command::Fetch { day: 9, force: false }),
+        ("fetch 9 --force", Command::Fetch { day: 9, force: true }),
+        ("submit 9 2", Command::Submit { day: 9, part: 2, input: Source::Default, history: None }),
+        ("submit 9 1 -i - --history h.txt", Command::Submit { day: 9, part: 1, input: Source::Stdin, history: Some("h.txt".into()) }),
+        ("gen 8", Command::Gen { day: 8, size: 1000, seed: 0 }),
+        ("gen 18 --size 20 --seed 42", Command::Gen { day: 18, size: 20, seed: 42 }),
+        ("crosscheck all", Command::Crosscheck { selection: Selection::All, cases: 100, size: 50, seed: 0 }),
+        ("crosscheck 9 --cases 5 --size 30 --seed 3", Command::Crosscheck { selection: Selection::Day(9), cases: 5, size: 30, seed: 3 }),
+    })]
+    fn valid(case: (&str, Command)) {
+        assert_eq!(parse(args(case.0)), Ok(case.1));
+    }
+
+    #[parameterized(case = {
+        ("", CliError::MissingCommand),
+        ("walk 7", CliError::UnknownCommand("walk".to_string())),
+        ("run", CliError::MissingArgument("<day>")),
+        ("run seven", CliError::InvalidValue { name: "<day>", value: "seven".to_string() }),
+        ("run 7 --part", CliError::MissingArgument("--part")),
+        ("run 7 --part 3", CliError::InvalidValue { name: "--part", value: "3".to_string() }),
+        ("run 7 --fast", CliError::UnexpectedArgument("--fast".to_string())),
+        ("list 7", CliError::UnexpectedArgument("7".to_string())),
+        ("run 7 --input", CliError::MissingArgument("--input")),
+        ("run all --input -", CliError::InputWithAll),
+        ("verify --answers", CliError::MissingArgument("--answers")),
+        ("verify 4 5", CliError::UnexpectedArgument("5".to_string())),
+        ("bench 1 --samples many", CliError::InvalidValue { name: "--samples", value: "many".to_string() }),
+        ("bench all --input x", CliError::InputWithAll),
+        ("run 1 --format xml", CliError::InvalidValue { name: "--format", value: "xml".to_string() }),
+        ("run 1 --verbosity loud", CliError::InvalidValue { name: "--verbosity", value: "loud".to_string() }),
+        ("run 1 --trace-file", CliError::MissingArgument("--trace-file")),
+        ("run all -j 0", CliError::InvalidValue { name: "--jobs", value: "0".to_string() }),
+        ("watch all", CliError::InvalidValue { name: "<day>", value: "all".to_string() }),
+        ("watch 3 --interval soon", CliError::InvalidValue { name: "--interval", value: "soon".to_string() }),
+        ("new", CliError::MissingArgument("<day>")),
+        ("new 19 20", CliError::UnexpectedArgument("20".to_string())),
+        ("fetch", CliError::MissingArgument("<day>")),
+        ("fetch all", CliError::InvalidValue { name: "<day>", value: "all".to_string() }),
+        ("submit 9", CliError::MissingArgument("--part")),
+        ("submit 9 3", CliError::InvalidValue { name: "--part", value: "3".to_string() }),
+        ("gen 5 --seed -1", CliError::InvalidValue { name: "--seed", value: "-1".to_string() }),
+        ("gen 5 --size", CliError::MissingArgument("--size")),
+        ("crosscheck", CliError::MissingArgument("<day>")),
+        ("crosscheck 1 --cases", CliError::MissingArgument("--cases")),
+    })]
+    fn invalid(case: (&str, CliError)) {
+        assert_eq!(parse(args(case.0)), Err(case.1));
+    }
+}
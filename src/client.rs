@@ -0,0 +1,329 @@
+use crate::input;
+use crate::parse::ParseError;
+use crate::solution::Answer;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2020;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            session: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+        }
+    }
+}
+
+impl Config {
+    pub fn default_path() -> Option<PathBuf> {
+        env::var_os("AOC_CONFIG").map(PathBuf::from).or_else(|| {
+            env::var_os("HOME").map(|home| {
+                Path::new(&home)
+                    .join(".config")
+                    .join("aoc2020")
+                    .join("config")
+            })
+        })
+    }
+
+    pub fn load() -> Result<Self, ClientError> {
+        let mut config = match Self::default_path() {
+            Some(path) if path.exists() => {
+                let content = fs::read_to_string(&path).map_err(|error| ClientError::Io {
+                    path: path.clone(),
+                    error,
+                })?;
+                Self::parse(&content).map_err(|error| ClientError::Config { path, error })?
+            }
+            _ => Self::default(),
+        };
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = base_url;
+        }
+        Ok(config)
+    }
+
+    pub fn parse(content: &str) -> Result<Self, ParseError> {
+        let mut config = Self::default();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or_else(|| {
+                ParseError::new(format!("invalid entry '{}'", line)).on_line(index + 1)
+            })?;
+            let value = value.trim().to_string();
+            match key.trim() {
+                "session" => config.session = Some(value),
+                "base_url" => config.base_url = value,
+                key => {
+                    return Err(ParseError::new(format!("unknown key '{}'", key)).on_line(index + 1))
+                }
+            }
+        }
+        Ok(config)
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    Config { path: PathBuf, error: ParseError },
+    Io { path: PathBuf, error: io::Error },
+    Status { url: String, status: u16 },
+    Transport { url: String, error: String },
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ClientError::*;
+        match self {
+            MissingSession => write!(
+                f,
+                "no session token, set AOC_SESSION or 'session' in the config file"
+            ),
+            Config { path, error } => write!(f, "{}: {}", path.display(), error),
+            Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            Status { url, status } => write!(f, "{} returned status {}", url, status),
+            Transport { url, error } => write!(f, "request to {} failed: {}", url, error),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Fetched {
+    pub path: PathBuf,
+    pub cached: bool,
+}
+
+pub struct Client {
+    config: Config,
+    data_dir: PathBuf,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Self {
+        Self::with_data_dir(config, input::data_dir())
+    }
+
+    pub fn with_data_dir(config: Config, data_dir: PathBuf) -> Self {
+        Self { config, data_dir }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!(
+            "{}/{}{}",
+            self.config.base_url.trim_end_matches('/'),
+            YEAR,
+            path
+        )
+    }
+
+    fn request(&self, method: &str, url: &str) -> Result<ureq::Request, ClientError> {
+        let session = self
+            .config
+            .session
+            .as_ref()
+            .ok_or(ClientError::MissingSession)?;
+        Ok(ureq::request(method, url)
+            .set("Cookie", &format!("session={}", session))
+            .set("User-Agent", "github.com/ojii/aoc2020"))
+    }
+
+    fn read(url: &str, result: Result<ureq::Response, ureq::Error>) -> Result<String, ClientError> {
+        match result {
+            Ok(response) => response
+                .into_string()
+                .map_err(|error| ClientError::Transport {
+                    url: url.to_string(),
+                    error: error.to_string(),
+                }),
+            Err(ureq::Error::Status(status, _)) => Err(ClientError::Status {
+                url: url.to_string(),
+                status,
+            }),
+            Err(error) => Err(ClientError::Transport {
+                url: url.to_string(),
+                error: error.to_string(),
+            }),
+        }
+    }
+
+    pub fn fetch(&self, day: u8, force: bool) -> Result<Fetched, ClientError> {
+        let path = input::path_in(&self.data_dir, day);
+        if !force && path.exists() {
+            return Ok(Fetched { path, cached: true });
+        }
+        let url = self.url(&format!("/day/{}/input", day));
+        let body = Self::read(&url, self.request("GET", &url)?.call())?;
+        let io_error = |error| ClientError::Io {
+            path: path.clone(),
+            error,
+        };
+        fs::create_dir_all(path.parent().expect("input path has a parent")).map_err(io_error)?;
+        fs::write(&path, body).map_err(io_error)?;
+        Ok(Fetched {
+            path,
+            cached: false,
+        })
+    }
+
+    pub fn submit(&self, day: u8, part: u8, answer: &Answer) -> Result<String, ClientError> {
+        let url = self.url(&format!("/day/{}/answer", day));
+        let response = self.request("POST", &url)?.send_form(&[
+            ("level", &part.to_string()),
+            ("answer", &answer.to_string()),
+        ]);
+        Self::read(&url, response)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    pub struct Server {
+        pub url: String,
+        pub requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    pub fn serve(status: u16, body: &'static str) -> Server {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind mock server");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                        break;
+                    }
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap_or(0);
+                    }
+                    request += &line;
+                }
+                let mut content = vec![0; length];
+                std::io::Read::read_exact(&mut reader, &mut content).ok();
+                request += &String::from_utf8_lossy(&content);
+                log.lock().unwrap().push(request);
+                let mut stream = stream;
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .ok();
+            }
+        });
+        Server { url, requests }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc2020-{}-{}", name, std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        dir
+    }
+
+    fn client(url: &str, data_dir: PathBuf) -> Client {
+        Client::with_data_dir(
+            Config {
+                session: Some("secret".to_string()),
+                base_url: url.to_string(),
+            },
+            data_dir,
+        )
+    }
+
+    #[test]
+    fn parse_config() {
+        assert_eq!(
+            Config::parse("# token\nsession = abc\nbase_url = http://localhost:8000\n"),
+            Ok(Config {
+                session: Some("abc".to_string()),
+                base_url: "http://localhost:8000".to_string(),
+            })
+        );
+    }
+
+    #[parameterized(case = {
+        ("session", "line 1: invalid entry 'session'"),
+        ("\ntoken = abc", "line 2: unknown key 'token'"),
+    })]
+    fn invalid_config(case: (&str, &str)) {
+        assert_eq!(
+            Config::parse(case.0).map_err(|error| error.to_string()),
+            Err(case.1.to_string())
+        );
+    }
+
+    #[test]
+    fn fetch_once() {
+        let server = mock::serve(200, "1\n2\n3\n");
+        let data_dir = temp_dir("fetch");
+        let client = client(&server.url, data_dir.clone());
+        let first = client.fetch(4, false).unwrap();
+        let second = client.fetch(4, false).unwrap();
+        assert!(!first.cached);
+        assert!(second.cached);
+        assert_eq!(fs::read_to_string(&first.path).unwrap(), "1\n2\n3\n");
+        let requests = server.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2020/day/4/input "));
+        assert!(requests[0].contains("session=secret"));
+        fs::remove_dir_all(data_dir).ok();
+    }
+
+    #[test]
+    fn fetch_status_error() {
+        let server = mock::serve(404, "not found");
+        let data_dir = temp_dir("status");
+        let result = client(&server.url, data_dir.clone()).fetch(5, false);
+        assert!(matches!(
+            result,
+            Err(ClientError::Status { status: 404, .. })
+        ));
+        assert!(!input::path_in(&data_dir, 5).exists());
+    }
+
+    #[test]
+    fn missing_session() {
+        let client = Client::with_data_dir(Config::default(), temp_dir("session"));
+        assert!(matches!(
+            client.fetch(6, false),
+            Err(ClientError::MissingSession)
+        ));
+    }
+}
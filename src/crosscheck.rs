@@ -0,0 +1,228 @@
+use crate::gen::{self, GenError};
+use crate::registry::Day;
+use crate::solution::Answer;
+use itertools::Itertools;
+use std::cell::Cell;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Mismatch {
+    pub day: u8,
+    pub part: u8,
+    pub seed: u64,
+    pub input: String,
+    pub answers: Vec<(&'static str, Option<Answer>)>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let answers = self
+            .answers
+            .iter()
+            .map(|(name, answer)| match answer {
+                Some(answer) => format!("{} = {}", name, answer),
+                None => format!("{} panicked", name),
+            })
+            .join(", ");
+        write!(
+            f,
+            "day {} part {} (seed {}): {}\n{}",
+            self.day, self.part, self.seed, answers, self.input
+        )
+    }
+}
+
+pub fn parts(day: &Day) -> Vec<u8> {
+    day.parts()
+        .filter(|&part| day.solver.implementations(part).len() > 1)
+        .collect()
+}
+
+static QUIET_HOOK: Once = Once::new();
+
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    QUIET_HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                hook(info)
+            }
+        }));
+    });
+    let previous = QUIET.with(|quiet| quiet.replace(true));
+    let result = f();
+    QUIET.with(|quiet| quiet.set(previous));
+    result
+}
+
+fn answers(day: &Day, part: u8, input: &str) -> Option<Vec<(&'static str, Option<Answer>)>> {
+    let parsed = panic::catch_unwind(AssertUnwindSafe(|| day.solver.parse(input)))
+        .ok()?
+        .ok()?;
+    Some(
+        day.solver
+            .implementations(part)
+            .into_iter()
+            .map(|name| {
+                let answer =
+                    panic::catch_unwind(AssertUnwindSafe(|| parsed.solve_with(part, name)))
+                        .ok()
+                        .flatten();
+                (name, answer)
+            })
+            .collect(),
+    )
+}
+
+fn disagreement(day: &Day, part: u8, input: &str) -> Option<Vec<(&'static str, Option<Answer>)>> {
+    answers(day, part, input)
+        .filter(|answers| !answers.iter().map(|(_, answer)| answer).all_equal())
+}
+
+fn without<T: Copy>(items: &[T], skip: usize) -> impl Iterator<Item = T> + '_ {
+    items
+        .iter()
+        .enumerate()
+        .filter(move |&(index, _)| index != skip)
+        .map(|(_, item)| *item)
+}
+
+fn candidates(input: &str) -> Vec<String> {
+    let lines = input.split_inclusive('\n').collect_vec();
+    let mut candidates = (0..lines.len())
+        .map(|skip| without(&lines, skip).collect::<String>())
+        .collect_vec();
+    for (index, line) in lines.iter().enumerate() {
+        let fields = line.trim_end_matches('\n').split(',').collect_vec();
+        if fields.len() < 2 {
+            continue;
+        }
+        for skip in 0..fields.len() {
+            candidates.push(format!(
+                "{}{}\n{}",
+                lines[..index].concat(),
+                without(&fields, skip).join(","),
+                lines[index + 1..].concat()
+            ));
+        }
+    }
+    candidates
+}
+
+pub fn shrink(day: &Day, part: u8, input: &str) -> String {
+    let mut input = input.to_string();
+    while let Some(smaller) = candidates(&input)
+        .into_iter()
+        .find(|candidate| disagreement(day, part, candidate).is_some())
+    {
+        input = smaller;
+    }
+    input
+}
+
+pub fn run(day: &Day, cases: usize, size: usize, seed: u64) -> Result<Vec<Mismatch>, GenError> {
+    let parts = parts(day);
+    let mut mismatches = Vec::new();
+    quietly(|| {
+        for part in parts {
+            for seed in (seed..).take(cases) {
+                let input = gen::generate(day.number, size, seed)?;
+                if disagreement(day, part, &input).is_some() {
+                    let input = shrink(day, part, &input);
+                    mismatches.push(Mismatch {
+                        day: day.number,
+                        part,
+                        seed,
+                        answers: disagreement(day, part, &input).unwrap_or_default(),
+                        input,
+                    });
+                    break;
+                }
+            }
+        }
+        Ok(mismatches)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::{self, ParseError};
+    use crate::registry;
+    use crate::solution::{Implementation, Solution};
+    use parameterized::parameterized;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<i64>;
+
+        const PARTS: u8 = 1;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            parse::lines(input, |line| parse::number(line, line))
+        }
+
+        fn part1(input: &Self::Input) -> Answer {
+            input.iter().sum::<i64>().into()
+        }
+
+        fn alternatives() -> Vec<Implementation<Self::Input>> {
+            vec![Implementation {
+                name: "small",
+                part: 1,
+                solve: |input| input.iter().filter(|&&num| num < 5).sum::<i64>().into(),
+            }]
+        }
+    }
+
+    const SUM: Day = Day {
+        number: 0,
+        solver: &Sum,
+    };
+
+    #[test]
+    fn shrinks_to_minimal_input() {
+        assert_eq!(
+            disagreement(&SUM, 1, "1\n7\n3\n9\n"),
+            Some(vec![
+                ("default", Some(Answer::Int(20))),
+                ("small", Some(Answer::Int(4)))
+            ])
+        );
+        assert_eq!(shrink(&SUM, 1, "1\n7\n3\n9\n"), "9\n");
+        assert_eq!(disagreement(&SUM, 1, "1\n3\n"), None);
+    }
+
+    #[test]
+    fn quiet_only_inside() {
+        assert!(!QUIET.with(Cell::get));
+        assert!(quietly(
+            || quietly(|| QUIET.with(Cell::get)) && QUIET.with(Cell::get)
+        ));
+        assert!(!QUIET.with(Cell::get));
+        let other = quietly(|| std::thread::spawn(|| QUIET.with(Cell::get)).join());
+        assert_eq!(other.ok(), Some(false));
+    }
+
+    #[test]
+    fn field_candidates() {
+        assert_eq!(
+            candidates("5\n7,x,13\n"),
+            vec!["7,x,13\n", "5\n", "5\nx,13\n", "5\n7,13\n", "5\n7,x\n"]
+        );
+    }
+
+    #[parameterized(day = { 1, 9, 13, 14 })]
+    fn implementations_agree(day: u8) {
+        let day = registry::find(day).unwrap();
+        assert!(!parts(day).is_empty());
+        assert_eq!(run(day, 10, 40, 0), Ok(Vec::new()));
+    }
+}
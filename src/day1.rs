@@ -1,18 +1,128 @@
-use crate::utils;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Implementation, Solution};
 use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
 
-fn find_entries(src: &[i32], num: i32, target: i32) -> Option<Vec<i32>> {
-    (0..num)
-        .map(|_| src)
-        .multi_cartesian_product()
-        .find(|candidates| candidates.iter().map(|a| a.clone()).sum::<i32>() == target)
-        .map(|v| v.iter().map(|&a| a.clone()).collect())
+const TARGET: i32 = 2020;
+
+fn find_entries(src: &[i32], num: usize, target: i32) -> Option<Vec<i32>> {
+    src.iter()
+        .copied()
+        .combinations(num)
+        .find(|candidates| candidates.iter().sum::<i32>() == target)
+}
+
+fn find_pair(src: &[i32], target: i32) -> Option<Vec<i32>> {
+    let mut seen = HashSet::new();
+    src.iter().find_map(|&entry| {
+        if seen.contains(&(target - entry)) {
+            Some(vec![target - entry, entry])
+        } else {
+            seen.insert(entry);
+            None
+        }
+    })
+}
+
+fn find_triple(src: &[i32], target: i32) -> Option<Vec<i32>> {
+    src.iter().enumerate().find_map(|(index, &entry)| {
+        find_pair(&src[index + 1..], target - entry).map(|mut pair| {
+            pair.insert(0, entry);
+            pair
+        })
+    })
+}
+
+fn product(entries: Option<Vec<i32>>) -> Answer {
+    entries
+        .expect("failed to find matching entries")
+        .iter()
+        .product::<i32>()
+        .into()
 }
 
-pub fn run() {
-    let input = utils::str_to_ints(include_str!("data/1/1"));
-    let result = find_entries(&input, 2, 2020).expect("failed to find matching entries");
-    println!("{:?} => {}", result, result.iter().fold(1, |a, b| a * b));
-    let result = find_entries(&input, 3, 2020).expect("failed to find matching entries");
-    println!("{:?} => {}", result, result.iter().fold(1, |a, b| a * b));
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let small = loop {
+        let pair = rng.gen_range(1..TARGET / 2);
+        let first = rng.gen_range(1..TARGET / 2);
+        let second = rng.gen_range(1..TARGET / 2);
+        let third = TARGET - first - second;
+        let small = [pair, first, second, third];
+        if (1..TARGET / 2).contains(&third)
+            && small.iter().unique().count() == small.len()
+            && small
+                .iter()
+                .tuple_combinations()
+                .all(|(a, b)| a + b != pair)
+            && small
+                .iter()
+                .tuple_combinations()
+                .filter(|(a, b, c)| *a + *b + *c == TARGET)
+                .count()
+                == 1
+        {
+            break small;
+        }
+    };
+    let forbidden: HashSet<i32> = small
+        .iter()
+        .map(|entry| TARGET - entry)
+        .chain(
+            small
+                .iter()
+                .tuple_combinations()
+                .map(|(a, b)| TARGET - a - b),
+        )
+        .collect();
+    let mut large = (TARGET / 2 + 1..TARGET)
+        .filter(|entry| !forbidden.contains(entry))
+        .collect_vec();
+    large.shuffle(rng);
+    let mut entries = large
+        .into_iter()
+        .take(size - 5)
+        .chain(small.iter().copied())
+        .chain(std::iter::once(TARGET - small[0]))
+        .collect_vec();
+    entries.shuffle(rng);
+    entries
+        .into_iter()
+        .map(|entry| format!("{}\n", entry))
+        .collect()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::lines(input, |line| parse::number(line, line))
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        product(find_pair(input, TARGET))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(product(find_triple(input, TARGET)))
+    }
+
+    fn alternatives() -> Vec<Implementation<Self::Input>> {
+        vec![
+            Implementation {
+                name: "brute-force",
+                part: 1,
+                solve: |input| product(find_entries(input, 2, TARGET)),
+            },
+            Implementation {
+                name: "brute-force",
+                part: 2,
+                solve: |input| product(find_entries(input, 3, TARGET)),
+            },
+        ]
+    }
 }
@@ -1,22 +1,35 @@
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
 use std::collections::HashSet;
 
-pub fn run() {
-    let input = include_str!("data/10/1");
-    let mut nums: HashSet<u32> = input
-        .lines()
-        .flat_map(|s| s.parse::<u32>().ok())
-        .chain((0..=0))
-        .collect();
-    nums.insert(nums.iter().max().expect("empty input") + 3);
-    let (ones, threes) =
-        nums.iter()
-            .sorted()
-            .tuple_windows::<(_, _)>()
-            .fold((0, 0), |(acc1, acc3), (a, b)| match b - a {
-                1 => (acc1 + 1, acc3),
-                3 => (acc1, acc3 + 1),
-                _ => (acc1, acc3),
-            });
-    println!("{} * {} = {:?}", ones, threes, ones * threes);
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = HashSet<u32>;
+
+    const PARTS: u8 = 1;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut nums: HashSet<u32> = parse::lines(input, |line| parse::number(line, line))?
+            .into_iter()
+            .chain(0..=0)
+            .collect();
+        nums.insert(nums.iter().max().expect("empty input") + 3);
+        Ok(nums)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let (ones, threes) =
+            input
+                .iter()
+                .sorted()
+                .tuple_windows::<(_, _)>()
+                .fold((0, 0), |(acc1, acc3), (a, b)| match b - a {
+                    1 => (acc1 + 1, acc3),
+                    3 => (acc1, acc3 + 1),
+                    _ => (acc1, acc3),
+                });
+        (ones * threes).into()
+    }
 }
@@ -1,15 +1,14 @@
-use crate::day11::Seat::Empty;
 use crate::maybe_from::MaybeFrom;
-use crate::render::{render, Render};
-use crate::twod::{Point, NEIGHBORS};
-use crate::utils::Counter;
-use itertools::Itertools;
-use std::collections::HashMap;
-use std::iter::FromIterator;
-
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+use crate::parse::ParseError;
+use crate::solution::{Answer, Solution};
+use crate::twod::{self, Grid, Neighborhood, Point};
+use std::convert::TryFrom;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, MaybeFrom)]
 enum Seat {
+    #[token('#')]
     Occupied,
+    #[token('L')]
     Empty,
 }
 
@@ -20,30 +19,6 @@ impl Seat {
             Seat::Empty => false,
         }
     }
-
-    fn is_empty(&self) -> bool {
-        !self.is_occupied()
-    }
-}
-
-impl Render for Seat {
-    fn render(&self) -> String {
-        match self {
-            Seat::Occupied => "#",
-            Seat::Empty => "L",
-        }
-        .to_string()
-    }
-}
-
-impl MaybeFrom<char> for Seat {
-    fn maybe_from(value: char) -> Option<Self> {
-        match value {
-            'L' => Some(Seat::Empty),
-            '#' => Some(Seat::Occupied),
-            _ => None,
-        }
-    }
 }
 
 enum Evolution {
@@ -52,144 +27,111 @@ enum Evolution {
 }
 
 #[derive(Debug, Clone)]
-struct WaitingArea {
-    seats: HashMap<Point<usize>, Seat>,
-    max: Point<usize>,
+pub struct WaitingArea {
+    seats: Grid<Option<Seat>>,
 }
 
-impl WaitingArea {
-    fn new(seats: HashMap<Point<usize>, Seat>) -> Self {
-        let max = seats
-            .keys()
-            .max()
-            .cloned()
-            .unwrap_or_else(|| Point::new(0, 0));
-        Self { seats, max }
-    }
-}
+impl TryFrom<&str> for WaitingArea {
+    type Error = ParseError;
 
-impl From<&str> for WaitingArea {
-    fn from(input: &str) -> Self {
-        Self::new(HashMap::from_iter(input.lines().enumerate().flat_map(
-            |(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .flat_map(move |(x, c)| Seat::maybe_from(c).map(|seat| (Point { x, y }, seat)))
-            },
-        )))
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        twod::parse_grid_with_empty(input, '.').map(|seats| Self { seats })
     }
 }
 
+type Neighbors = Grid<Vec<Point<usize>>>;
+
 impl WaitingArea {
-    fn evolve<'a, N, I>(&'a self, neighbors: N, tolerance: usize) -> Evolution
-    where
-        I: Iterator<Item = Seat>,
-        N: Fn(&'a Self, &'a Point<usize>) -> I + 'a,
-    {
+    fn occupied(&self, points: &[Point<usize>]) -> usize {
+        points
+            .iter()
+            .filter(|point| matches!(self.seats[**point], Some(Seat::Occupied)))
+            .count()
+    }
+
+    fn evolve(&self, neighbors: &Neighbors, tolerance: usize) -> Evolution {
         let mut stable = true;
-        let waiting_area = WaitingArea::new(HashMap::from_iter(self.seats.iter().map(
-            |(point, seat)| {
-                match seat {
-                    Seat::Occupied => {
-                        if neighbors(&self, point)
-                            .filter(|seat| seat.is_occupied())
-                            .count()
-                            >= tolerance
-                        {
-                            stable = false;
-                            (point.clone(), Seat::Empty)
-                        } else {
-                            (point.clone(), Seat::Occupied)
-                        }
-                    }
-                    Seat::Empty => {
-                        if neighbors(&self, point).all(|seat| seat.is_empty()) {
-                            stable = false;
-                            (point.clone(), Seat::Occupied)
-                        } else {
-                            (point.clone(), Seat::Empty)
-                        }
-                    }
+        let seats = self.seats.map(|point, seat| match seat {
+            Some(Seat::Occupied) => {
+                if self.occupied(&neighbors[point]) >= tolerance {
+                    stable = false;
+                    Some(Seat::Empty)
+                } else {
+                    Some(Seat::Occupied)
                 }
-            },
-        )));
+            }
+            Some(Seat::Empty) => {
+                if self.occupied(&neighbors[point]) == 0 {
+                    stable = false;
+                    Some(Seat::Occupied)
+                } else {
+                    Some(Seat::Empty)
+                }
+            }
+            None => None,
+        });
         if stable {
-            Evolution::Stabilized(waiting_area)
+            Evolution::Stabilized(WaitingArea { seats })
         } else {
-            Evolution::Evolved(waiting_area)
+            Evolution::Evolved(WaitingArea { seats })
         }
     }
 }
 
-fn direct_neighbors<'a>(
-    waiting_area: &'a WaitingArea,
-    point: &'a Point<usize>,
-) -> impl Iterator<Item = Seat> + 'a {
-    point
-        .neighbors()
-        .flat_map(move |point| waiting_area.seats.get(&point).cloned())
+fn direct_neighbors(waiting_area: &WaitingArea) -> Neighbors {
+    let seats = &waiting_area.seats;
+    seats.map(|point, _| {
+        seats
+            .neighbors_in(&point, Neighborhood::Moore)
+            .filter(|neighbor| seats[*neighbor].is_some())
+            .collect()
+    })
 }
 
-fn full_neighbors<'a>(
-    waiting_area: &'a WaitingArea,
-    point: &'a Point<usize>,
-) -> impl Iterator<Item = Seat> + 'a {
-    NEIGHBORS.iter().flat_map(move |neighbor| {
-        for distance in Counter::new() {
-            match neighbor.point(point, distance) {
-                Some(other) => {
-                    if other > waiting_area.max {
-                        return None;
-                    } else {
-                        match waiting_area.seats.get(&other) {
-                            Some(seat) => return Some(seat.clone()),
-                            None => (),
-                        }
-                    }
-                }
-                None => break,
-            }
-        }
-        return None;
-    })
+fn full_neighbors(waiting_area: &WaitingArea) -> Neighbors {
+    waiting_area
+        .seats
+        .visibility(Neighborhood::Moore, Option::is_some)
 }
 
-fn evolve_until_stability<'a, I, N>(
+fn evolve_until_stability(
     waiting_area: &WaitingArea,
-    neighbors: N,
+    neighbors: fn(&WaitingArea) -> Neighbors,
     tolerance: usize,
-) -> WaitingArea
-where
-    I: Iterator<Item = Seat>,
-    N: Fn(&'a WaitingArea, &'a Point<usize>) -> I + 'a,
-{
+) -> WaitingArea {
+    let neighbors = neighbors(waiting_area);
     let mut waiting_area = waiting_area.clone();
     loop {
-        match waiting_area.evolve(direct_neighbors, tolerance) {
+        match waiting_area.evolve(&neighbors, tolerance) {
             Evolution::Evolved(new_waiting_area) => waiting_area = new_waiting_area,
             Evolution::Stabilized(stable_waiting_area) => return stable_waiting_area,
         }
     }
 }
 
-pub fn run() {
-    let waiting_area = WaitingArea::from(include_str!("data/11/1"));
-    let stable = evolve_until_stability(&waiting_area, direct_neighbors, 4);
-    println!(
-        "{}",
-        stable
-            .seats
-            .values()
-            .filter(|seat| seat.is_occupied())
-            .count()
-    );
-    let stable = evolve_until_stability(&waiting_area, full_neighbors, 5);
-    println!(
-        "{}",
-        stable
-            .seats
-            .values()
-            .filter(|seat| seat.is_occupied())
-            .count()
-    );
+fn count_occupied(waiting_area: &WaitingArea) -> usize {
+    waiting_area
+        .seats
+        .values()
+        .flatten()
+        .filter(|seat| seat.is_occupied())
+        .count()
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = WaitingArea;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        WaitingArea::try_from(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        count_occupied(&evolve_until_stability(input, direct_neighbors, 4)).into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(count_occupied(&evolve_until_stability(input, full_neighbors, 5)).into())
+    }
 }
@@ -1,10 +1,14 @@
 use crate::maybe_from::{MaybeFrom, MaybeInto};
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
 use crate::twod::{Point, Vector};
-use itertools::Itertools;
-use num::Integer;
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::convert::TryFrom;
+use std::fmt;
 
-#[derive(Debug, Copy, Clone)]
-enum Instruction {
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Instruction {
     North(i32),
     South(i32),
     East(i32),
@@ -14,28 +18,55 @@ enum Instruction {
     Forward(i32),
 }
 
-impl MaybeFrom<&str> for Instruction {
-    fn maybe_from(value: &str) -> Option<Self> {
+impl TryFrom<&str> for Instruction {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
         use Instruction::*;
         let mut chars = value.chars();
-        let code = chars.next()?;
-        let num = chars.join("").parse::<i32>().ok()?;
+        let code = chars
+            .next()
+            .ok_or_else(|| ParseError::at(1, "missing action"))?;
+        let num = parse::number(value, chars.as_str())?;
+        if matches!(code, 'L' | 'R') && num % 90 != 0 {
+            return Err(parse::error_at(
+                value,
+                chars.as_str(),
+                format!("turn of {} degrees is not a multiple of 90", num),
+            ));
+        }
         match code {
-            'N' => Some(North(num)),
-            'S' => Some(South(num)),
-            'E' => Some(East(num)),
-            'W' => Some(West(num)),
-            'L' => Some(Left(num)),
-            'R' => Some(Right(num)),
-            'F' => Some(Forward(num)),
-            _ => None,
+            'N' => Ok(North(num)),
+            'S' => Ok(South(num)),
+            'E' => Ok(East(num)),
+            'W' => Ok(West(num)),
+            'L' => Ok(Left(num)),
+            'R' => Ok(Right(num)),
+            'F' => Ok(Forward(num)),
+            _ => Err(ParseError::at(1, format!("unknown action '{}'", code))),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use Instruction::*;
+        match self {
+            North(num) => write!(f, "N{}", num),
+            South(num) => write!(f, "S{}", num),
+            East(num) => write!(f, "E{}", num),
+            West(num) => write!(f, "W{}", num),
+            Left(num) => write!(f, "L{}", num),
+            Right(num) => write!(f, "R{}", num),
+            Forward(num) => write!(f, "F{}", num),
         }
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Default)]
 enum Direction {
     North,
+    #[default]
     East,
     South,
     West,
@@ -49,7 +80,7 @@ impl Direction {
     fn right(&self, degrees: i32) -> Direction {
         (self.degrees() + degrees)
             .maybe_into()
-            .expect(&format!("Invalid turn: {}", degrees))
+            .unwrap_or_else(|| panic!("Invalid turn: {}", degrees))
     }
 
     fn vector(&self, distance: i32) -> Vector<i32> {
@@ -86,12 +117,6 @@ impl MaybeFrom<i32> for Direction {
     }
 }
 
-impl Default for Direction {
-    fn default() -> Self {
-        Direction::East
-    }
-}
-
 #[derive(Debug)]
 struct Ship {
     position: Point<i32>,
@@ -137,19 +162,78 @@ impl Default for Ship {
     }
 }
 
-pub fn run() {
-    let instructions = include_str!("data/12/1")
-        .lines()
-        .flat_map(|line| Instruction::maybe_from(line))
-        .collect_vec();
-    let mut ship = Ship::default();
-    for instruction in &instructions {
-        ship.execute(instruction);
-    }
-    println!("{}", ship.position.manhattan_distance(&Point::default()));
-    let mut ship = Ship::default();
-    for instruction in &instructions {
-        ship.execute_waypoint(instruction);
-    }
-    println!("{}", ship.position.manhattan_distance(&Point::default()));
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let action = "NSEWLRF".as_bytes()[rng.gen_range(0..7)] as char;
+            let value = match action {
+                'L' | 'R' => rng.gen_range(1..=3) * 90,
+                _ => rng.gen_range(1..=100),
+            };
+            format!("{}{}\n", action, value)
+        })
+        .collect()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::lines(input, |line| Instruction::try_from(line))
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let mut ship = Ship::default();
+        for instruction in input {
+            ship.execute(instruction);
+        }
+        ship.position.manhattan_distance(&Point::default()).into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        let mut ship = Ship::default();
+        for instruction in input {
+            ship.execute_waypoint(instruction);
+        }
+        Some(ship.position.manhattan_distance(&Point::default()).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render;
+    use parameterized::parameterized;
+    use rand::SeedableRng;
+
+    #[parameterized(case = {
+        ("F10\nL45", "line 2, column 2: turn of 45 degrees is not a multiple of 90"),
+        ("R-30", "line 1, column 2: turn of -30 degrees is not a multiple of 90"),
+        ("N3\nX4", "line 2, column 1: unknown action 'X'"),
+    })]
+    fn invalid_instructions(case: (&str, &str)) {
+        assert_eq!(
+            Day12::parse(case.0).map_err(|error| error.to_string()),
+            Err(case.1.to_string())
+        );
+    }
+
+    #[test]
+    fn turns() {
+        let instructions = Day12::parse("R270\nF10\nL-180\nF2").unwrap();
+        assert_eq!(Day12::part1(&instructions), Answer::Int(8));
+    }
+
+    #[parameterized(seed = { 0, 1, 2, 3, 4 })]
+    fn round_trip(seed: u64) {
+        let input = generate(&mut StdRng::seed_from_u64(seed), 30);
+        let instructions = Day12::parse(&input).unwrap();
+        assert_eq!(render::lines(&instructions), input);
+        assert_eq!(
+            Day12::parse(&render::lines(&instructions)),
+            Ok(instructions)
+        );
+    }
 }
@@ -1,7 +1,10 @@
-use crate::maybe_from::MaybeFrom;
-use crate::utils::Counter;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Implementation, Solution};
 use itertools::Itertools;
 use num::Integer;
+use rand::rngs::StdRng;
+use rand::seq::{index, SliceRandom};
+use rand::Rng;
 
 #[derive(Debug, Copy, Clone)]
 struct Bus {
@@ -15,7 +18,7 @@ impl Bus {
 }
 
 #[derive(Debug)]
-struct Airport {
+pub struct Airport {
     timestamp: usize,
     lines: Vec<Bus>,
 }
@@ -25,59 +28,58 @@ impl Airport {
         self.lines
             .iter()
             .min_by_key(|line| line.next_departure(&self.timestamp))
-            .map(|bus| {
-                (
-                    bus.next_departure(&self.timestamp) - self.timestamp,
-                    bus.clone(),
-                )
-            })
+            .map(|bus| (bus.next_departure(&self.timestamp) - self.timestamp, *bus))
     }
 }
 
-impl MaybeFrom<&str> for Airport {
-    fn maybe_from(value: &str) -> Option<Self> {
-        let (timestamp, lines) = value.split_once("\n")?;
-        Some(Self {
-            timestamp: timestamp.parse::<usize>().ok()?,
-            lines: lines
-                .split(",")
-                .flat_map(|id| id.parse::<usize>().ok())
-                .map(|id| Bus { id })
-                .collect(),
-        })
-    }
-}
-
-#[inline(always)]
-fn check_timestamp(timestamp: usize) -> bool {
-    timestamp % 13 == 0
-        && (timestamp + 3) % 41 == 0
-        && (timestamp + 13) % 569 == 0
-        && (timestamp + 15) % 29 == 0
-        && (timestamp + 32) & 19 == 0
-        && (timestamp + 36) % 23 == 0
-        && (timestamp + 44) % 937 == 0
-        && (timestamp + 50) % 37 == 0
-        && (timestamp + 61) % 17 == 0
-}
-
 #[derive(Debug)]
-struct Rule {
+pub struct Rule {
     offset: u64,
     bus_id: u64,
 }
 
-fn find_timestamp(rules: &str) -> u64 {
-    // Stolen from reddit
-    rules
-        .split(",")
+fn rules(source: &str, notes: &str) -> Result<Vec<Rule>, ParseError> {
+    let buses = parse::list(notes, ",", |bus| match bus {
+        "x" => Ok(None),
+        bus => match parse::number(source, bus)? {
+            0 => Err(parse::error_at(source, bus, "bus id must not be 0")),
+            bus_id => Ok(Some(bus_id)),
+        },
+    })?;
+    Ok(buses
+        .into_iter()
         .enumerate()
-        .flat_map(|(index, bus)| {
-            bus.parse::<u64>().ok().map(|bus_id| Rule {
+        .flat_map(|(index, bus_id)| {
+            bus_id.map(|bus_id| Rule {
                 offset: index as u64,
                 bus_id,
             })
         })
+        .collect())
+}
+
+fn check_timestamp(rules: &[Rule], timestamp: u64) -> bool {
+    rules
+        .iter()
+        .all(|rule| (timestamp + rule.offset).is_multiple_of(rule.bus_id))
+}
+
+fn sieve_timestamp(rules: &[Rule]) -> u64 {
+    let slowest = rules
+        .iter()
+        .max_by_key(|rule| rule.bus_id)
+        .expect("no bus lines");
+    let first = (slowest.bus_id - slowest.offset % slowest.bus_id) % slowest.bus_id;
+    (first..)
+        .step_by(slowest.bus_id as usize)
+        .find(|&timestamp| check_timestamp(rules, timestamp))
+        .expect("no timestamp found")
+}
+
+fn find_timestamp(rules: &[Rule]) -> u64 {
+    // Stolen from reddit
+    rules
+        .iter()
         .sorted_by_key(|rule| rule.bus_id)
         .fold(
             (0, 1),
@@ -95,18 +97,105 @@ fn find_timestamp(rules: &str) -> u64 {
         .0
 }
 
-pub fn run() {
-    let airport = Airport::maybe_from(include_str!("data/13/1")).expect("Failed to parse");
-    let (delay, bus) = airport.find_next_departure().expect("No departure found");
-    println!("{}", delay * bus.id);
-    println!(
-        "{}",
-        find_timestamp(
-            include_str!("data/13/1")
-                .lines()
-                .skip(1)
-                .next()
-                .expect("invalid input")
-        )
-    );
+const PRIMES: &[u64] = &[3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut ids = PRIMES.to_vec();
+    ids.shuffle(rng);
+    let mut notes = vec!["x".to_string(); size];
+    notes[0] = ids[0].to_string();
+    let others = rng.gen_range(1..=(size - 1).min(4));
+    for (position, id) in index::sample(rng, size - 1, others)
+        .into_iter()
+        .zip(&ids[1..])
+    {
+        notes[position + 1] = id.to_string();
+    }
+    format!("{}\n{}\n", rng.gen_range(1000..1_000_000), notes.join(","))
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = (Airport, Vec<Rule>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut lines = input.lines();
+        let timestamp = lines
+            .next()
+            .ok_or_else(|| ParseError::new("missing timestamp"))?;
+        let notes = lines
+            .next()
+            .ok_or_else(|| ParseError::new("missing bus lines").on_line(2))?;
+        let rules = rules(input, notes)?;
+        let airport = Airport {
+            timestamp: parse::number(input, timestamp)?,
+            lines: rules
+                .iter()
+                .map(|rule| Bus {
+                    id: rule.bus_id as usize,
+                })
+                .collect(),
+        };
+        Ok((airport, rules))
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let (delay, bus) = input.0.find_next_departure().expect("No departure found");
+        (delay * bus.id).into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(find_timestamp(&input.1).into())
+    }
+
+    fn alternatives() -> Vec<Implementation<Self::Input>> {
+        vec![Implementation {
+            name: "brute-force",
+            part: 2,
+            solve: |input| sieve_timestamp(&input.1).into(),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+
+    #[parameterized(case = {
+        ("939\n7,13,x,x,59", 295),
+        ("939\n7,13,x,x,59,x,31,19\n", 295),
+    })]
+    fn next_departure(case: (&str, usize)) {
+        let (airport, _) = Day13::parse(case.0).unwrap();
+        let (delay, bus) = airport.find_next_departure().unwrap();
+        assert_eq!(delay * bus.id, case.1);
+    }
+
+    #[parameterized(case = {
+        ("17,x,13,19", 3417),
+        ("67,7,59,61", 754018),
+        ("67,x,7,59,61", 779210),
+        ("1789,37,47,1889", 1202161486),
+    })]
+    fn timestamp(case: (&str, u64)) {
+        assert_eq!(find_timestamp(&rules(case.0, case.0).unwrap()), case.1);
+    }
+
+    #[parameterized(case = {
+        ("939\n7,13,?,59", "line 2, column 6: invalid number '?'"),
+        ("939\n7,,59", "line 2, column 3: invalid number ''"),
+        ("93a\n7,13", "line 1, column 1: invalid number '93a'"),
+        ("939", "line 2: missing bus lines"),
+        ("939\n7,x,0", "line 2, column 5: bus id must not be 0"),
+    })]
+    fn invalid_notes(case: (&str, &str)) {
+        assert_eq!(
+            Day13::parse(case.0)
+                .map(|_| ())
+                .map_err(|error| error.to_string()),
+            Err(case.1.to_string())
+        );
+    }
 }
@@ -1,25 +1,37 @@
 use crate::maybe_from::MaybeFrom;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Implementation, Solution};
 use intbits::Bits;
 use itertools::Itertools;
-use num::traits::real::Real;
+use rand::rngs::StdRng;
+use rand::seq::index;
+use rand::Rng;
 use std::collections::HashMap;
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
 use std::iter::FromIterator;
 
-#[derive(Debug, Copy, Clone)]
-struct Uint36 {
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Uint36 {
     bits: [bool; 36],
 }
 
 impl MaybeFrom<&str> for Uint36 {
     fn maybe_from(value: &str) -> Option<Self> {
-        value.parse::<u64>().ok().and_then(|i| Self::maybe_from(i))
+        value.parse::<u64>().ok().and_then(Self::maybe_from)
     }
 }
 
-impl Into<u64> for Uint36 {
-    fn into(self) -> u64 {
-        self.bits
+impl fmt::Display for Uint36 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", u64::from(*self))
+    }
+}
+
+impl From<Uint36> for u64 {
+    fn from(value: Uint36) -> Self {
+        value
+            .bits
             .iter()
             .enumerate()
             .fold(0u64, |acc, (index, bit)| acc.with_bit(35 - index, *bit))
@@ -38,8 +50,8 @@ impl MaybeFrom<u64> for Uint36 {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-struct BitMask {
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct BitMask {
     bits: [MaskBit; 36],
 }
 
@@ -81,27 +93,51 @@ impl BitMask {
                 .map(|(index, fluctuating_index)| (*fluctuating_index, index)),
         );
 
+        let resolve = |values: &[&bool]| -> u64 {
+            let bits = self
+                .bits
+                .iter()
+                .zip(address.bits.iter())
+                .enumerate()
+                .map(|(index, (mask, bit))| match mask {
+                    Zero => *bit,
+                    One => true,
+                    X => *values[indexmap[&index]],
+                })
+                .collect_vec()
+                .try_into()
+                .expect("failed to convert back");
+            Uint36 { bits }.into()
+        };
+
+        if fluctuating_indices.is_empty() {
+            return vec![resolve(&[])];
+        }
         (0..fluctuating_indices.len())
             .map(|_| &[true, false])
             .multi_cartesian_product()
-            .map(|values| {
-                let bits = self
-                    .bits
-                    .iter()
-                    .zip(address.bits.iter())
-                    .enumerate()
-                    .map(|(index, (mask, bit))| match mask {
-                        Zero => *bit,
-                        One => true,
-                        X => values[indexmap[&index]].clone(),
-                    })
-                    .collect_vec()
-                    .try_into()
-                    .expect("failed to convert back");
-                Uint36 { bits }.into()
-            })
+            .map(|values| resolve(&values))
             .collect_vec()
     }
+
+    fn select(&self, selected: fn(&MaskBit) -> bool) -> u64 {
+        self.bits
+            .iter()
+            .fold(0, |acc, bit| acc << 1 | selected(bit) as u64)
+    }
+
+    fn floating_addresses(&self, address: &Uint36) -> Vec<u64> {
+        let floating = self.select(|bit| matches!(bit, MaskBit::X));
+        let base =
+            (u64::from(*address) | self.select(|bit| matches!(bit, MaskBit::One))) & !floating;
+        let mut addresses = vec![base | floating];
+        let mut subset = floating;
+        while subset != 0 {
+            subset = (subset - 1) & floating;
+            addresses.push(base | subset);
+        }
+        addresses
+    }
 }
 
 impl Default for BitMask {
@@ -116,7 +152,7 @@ impl MaybeFrom<&str> for BitMask {
     fn maybe_from(value: &str) -> Option<Self> {
         value
             .chars()
-            .flat_map(|c| MaskBit::maybe_from(c))
+            .flat_map(MaskBit::maybe_from)
             .collect_vec()
             .try_into()
             .ok()
@@ -124,44 +160,82 @@ impl MaybeFrom<&str> for BitMask {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-enum MaskBit {
+impl fmt::Display for BitMask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.bits.iter().join(""))
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, MaybeFrom)]
+pub enum MaskBit {
+    #[token('0')]
     Zero,
+    #[token('1')]
     One,
+    #[token('X')]
     X,
 }
 
-impl MaybeFrom<char> for MaskBit {
-    fn maybe_from(value: char) -> Option<Self> {
-        use MaskBit::*;
-        match value {
-            '0' => Some(Zero),
-            '1' => Some(One),
-            'X' => Some(X),
-            _ => None,
-        }
-    }
-}
-
-#[derive(Debug)]
-enum Instruction {
+#[derive(Debug, Eq, PartialEq)]
+pub enum Instruction {
     Bitmask(BitMask),
     Write { location: u64, value: Uint36 },
 }
 
-impl MaybeFrom<&str> for Instruction {
-    fn maybe_from(value: &str) -> Option<Self> {
+impl TryFrom<&str> for Instruction {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
         use Instruction::*;
-        let (lhs, rhs) = value.split_once(" = ")?;
+        let (lhs, rhs) = parse::split(value, value, " = ")?;
         if lhs == "mask" {
-            BitMask::maybe_from(rhs).map(|mask| Bitmask(mask))
+            if let Some((offset, bit)) = rhs
+                .char_indices()
+                .find(|(_, bit)| MaskBit::maybe_from(*bit).is_none())
+            {
+                return Err(parse::error_at(
+                    value,
+                    &rhs[offset..],
+                    format!("invalid mask bit '{}'", bit),
+                ));
+            }
+            BitMask::maybe_from(rhs)
+                .map(Bitmask)
+                .ok_or_else(|| parse::error_at(value, rhs, "mask must be 36 bits"))
         } else {
-            lhs.chars()
-                .filter(|c| c.is_numeric())
-                .join("")
-                .parse::<u64>()
-                .ok()
-                .and_then(|location| Uint36::maybe_from(rhs).map(|value| Write { location, value }))
+            let location = parse::literal(value, lhs, "mem[")?;
+            let location = parse::terminated(value, location, "]")?;
+            let address: u64 = parse::number(value, location)?;
+            if address >= 1 << 36 {
+                return Err(parse::error_at(
+                    value,
+                    location,
+                    format!("address {} does not fit in 36 bits", address),
+                ));
+            }
+            let number: u64 = parse::number(value, rhs)?;
+            if number >= 1 << 36 {
+                return Err(parse::error_at(
+                    value,
+                    rhs,
+                    format!("value {} does not fit in 36 bits", number),
+                ));
+            }
+            Uint36::maybe_from(number)
+                .map(|value| Write {
+                    location: address,
+                    value,
+                })
+                .ok_or_else(|| parse::error_at(value, rhs, "invalid value"))
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Bitmask(mask) => write!(f, "mask = {}", mask),
+            Instruction::Write { location, value } => write!(f, "mem[{}] = {}", location, value),
         }
     }
 }
@@ -183,12 +257,17 @@ impl Memory {
         }
     }
 
-    fn apply_v2(&mut self, instruction: &Instruction) {
+    fn apply_v2(
+        &mut self,
+        instruction: &Instruction,
+        addresses: fn(&BitMask, &Uint36) -> Vec<u64>,
+    ) {
         use Instruction::*;
         match instruction {
             Bitmask(mask) => self.mask = *mask,
             Write { location, value } => {
-                for address in self.mask.transform_memory_address(
+                for address in addresses(
+                    &self.mask,
                     &Uint36::maybe_from(*location).expect("failed to transform"),
                 ) {
                     self.ram.insert(address, *value);
@@ -198,39 +277,99 @@ impl Memory {
     }
 }
 
-pub fn run() {
-    let instructions = include_str!("data/14/1")
-        .lines()
-        .flat_map(|line| Instruction::maybe_from(line))
-        .collect_vec();
+fn run_v2(input: &[Instruction], addresses: fn(&BitMask, &Uint36) -> Vec<u64>) -> Answer {
     let mut memory = Memory::default();
-    for instruction in &instructions {
-        memory.apply_v1(instruction);
+    for instruction in input {
+        memory.apply_v2(instruction, addresses);
     }
-    println!(
-        "{}",
-        memory
-            .ram
-            .values()
-            .map(|uint36| -> u64 { (*uint36).into() })
-            .sum::<u64>()
-    );
-    let mut memory = Memory::default();
-    for instruction in &instructions {
-        memory.apply_v2(instruction);
+    sum_memory(&memory).into()
+}
+
+fn sum_memory(memory: &Memory) -> u64 {
+    memory
+        .ram
+        .values()
+        .map(|uint36| -> u64 { (*uint36).into() })
+        .sum::<u64>()
+}
+
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut lines = Vec::with_capacity(size);
+    while lines.len() < size {
+        let mut mask = (0..36)
+            .map(|_| match rng.gen_bool(0.5) {
+                true => '1',
+                false => '0',
+            })
+            .collect_vec();
+        let floating = rng.gen_range(0..=9);
+        for index in index::sample(rng, 36, floating) {
+            mask[index] = 'X';
+        }
+        lines.push(format!("mask = {}", mask.into_iter().collect::<String>()));
+        for _ in 0..rng.gen_range(1..=8) {
+            lines.push(format!(
+                "mem[{}] = {}",
+                rng.gen_range(0..1 << 16),
+                rng.gen_range(0u64..1 << 36)
+            ));
+        }
     }
-    println!(
-        "{}",
-        memory
-            .ram
-            .values()
-            .map(|uint36| -> u64 { (*uint36).into() })
-            .sum::<u64>()
-    );
+    lines.truncate(size);
+    lines.into_iter().map(|line| line + "\n").collect()
 }
 
+fn in_order() -> impl FnMut(&str) -> Result<Instruction, ParseError> {
+    let mut masked = false;
+    move |line| match Instruction::try_from(line)? {
+        Instruction::Write { .. } if !masked => {
+            Err(ParseError::new("memory write before the first mask"))
+        }
+        instruction => {
+            masked |= matches!(instruction, Instruction::Bitmask(_));
+            Ok(instruction)
+        }
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::lines(input, in_order())
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let mut memory = Memory::default();
+        for instruction in input {
+            memory.apply_v1(instruction);
+        }
+        sum_memory(&memory).into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(run_v2(input, BitMask::floating_addresses))
+    }
+
+    fn alternatives() -> Vec<Implementation<Self::Input>> {
+        vec![Implementation {
+            name: "enumeration",
+            part: 2,
+            solve: |input| run_v2(input, BitMask::transform_memory_address),
+        }]
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse::Mode;
+    use crate::render;
+    use parameterized::parameterized;
+    use rand::SeedableRng;
+
     #[test]
     fn test_11() {
         let u36 = Uint36::maybe_from("11").expect("failed to parse");
@@ -259,5 +398,85 @@ mod tests {
                 .collect_vec(),
             vec![26, 27, 58, 59]
         );
+        let fixed =
+            BitMask::maybe_from("000000000000000000000000000000010010").expect("failed to parse");
+        assert_eq!(fixed.transform_memory_address(&address), vec![58]);
+        assert_eq!(fixed.floating_addresses(&address), vec![58]);
+        assert_eq!(
+            mask.floating_addresses(&address)
+                .iter()
+                .sorted()
+                .cloned()
+                .collect_vec(),
+            vec![26, 27, 58, 59]
+        );
+    }
+
+    #[test]
+    fn invalid_instructions() {
+        assert_eq!(
+            Day14::parse("mask = 0000000000000000000000000000000000XX\nmask = 00000000000000000000000000000001X02X")
+                .err()
+                .map(|error| error.to_string()),
+            Some("line 2, column 42: invalid mask bit '2'".to_string())
+        );
+        assert_eq!(
+            Day14::parse("mem[8] = 68719476736")
+                .err()
+                .map(|error| error.to_string()),
+            Some("line 1, column 10: value 68719476736 does not fit in 36 bits".to_string())
+        );
+        assert_eq!(
+            Day14::parse("\nmem[8] = 11\nmask = 00000000000000000000000000000001X01X")
+                .err()
+                .map(|error| error.to_string()),
+            Some("line 2: memory write before the first mask".to_string())
+        );
+        assert_eq!(
+            Day14::parse("mask = 00000000000000000000000000000001X01X\nmem[8] = 11")
+                .map(|instructions| instructions.len()),
+            Ok(2)
+        );
+    }
+
+    #[test]
+    fn lenient_write_before_mask() {
+        let input = "mask = 0X1\nmem[8] = 11\nmask = 00000000000000000000000000000001X01X\n";
+        let lines = parse::lines_with(input, Mode::Lenient, in_order()).unwrap();
+        assert_eq!(lines.values.len(), 1);
+        assert_eq!(
+            lines.skipped.iter().map(ToString::to_string).collect_vec(),
+            vec![
+                "line 1, column 8: mask must be 36 bits",
+                "line 2: memory write before the first mask"
+            ]
+        );
+    }
+
+    #[parameterized(case = {
+        ("mask = 0X1", "column 8: mask must be 36 bits"),
+        ("mask 0X1", "column 1: expected ' = '"),
+        ("mex[8] = 11", "column 1: expected 'mem['"),
+        ("mem[8 = 11", "column 6: expected ']'"),
+        ("mem[a] = 11", "column 5: invalid number 'a'"),
+        ("mem[8] = -1", "column 10: invalid number '-1'"),
+        ("mem[68719476736] = 5", "column 5: address 68719476736 does not fit in 36 bits"),
+    })]
+    fn invalid_instruction(case: (&str, &str)) {
+        assert_eq!(
+            Instruction::try_from(case.0).map_err(|error| error.to_string()),
+            Err(case.1.to_string())
+        );
+    }
+
+    #[parameterized(seed = { 0, 1, 2, 3, 4 })]
+    fn round_trip(seed: u64) {
+        let input = generate(&mut StdRng::seed_from_u64(seed), 30);
+        let instructions = Day14::parse(&input).unwrap();
+        assert_eq!(render::lines(&instructions), input);
+        assert_eq!(
+            Day14::parse(&render::lines(&instructions)),
+            Ok(instructions)
+        );
     }
 }
@@ -1,3 +1,5 @@
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 
@@ -12,19 +14,19 @@ impl LastCalls {
         use LastCalls::*;
         match self {
             Single(n) => Double(*n, other),
-            Double(a, b) => Double(*b, other),
+            Double(_, b) => Double(*b, other),
         }
     }
 }
 
 fn memory_game(numbers: &[u64], num: usize) -> u64 {
     let mut memory: HashMap<u64, LastCalls> = HashMap::new();
-    let (index, mut last) = numbers
+    let (index, last) = numbers
         .iter()
         .enumerate()
         .map(|(index, number)| {
             match memory.entry(*number) {
-                Entry::Vacant(mut entry) => {
+                Entry::Vacant(entry) => {
                     entry.insert(LastCalls::Single(index + 1));
                 }
                 Entry::Occupied(mut entry) => {
@@ -42,7 +44,7 @@ fn memory_game(numbers: &[u64], num: usize) -> u64 {
             Some(LastCalls::Double(a, b)) => (b - a) as u64,
         };
         match memory.entry(new_last) {
-            Entry::Vacant(mut entry) => {
+            Entry::Vacant(entry) => {
                 entry.insert(LastCalls::Single(turn));
             }
             Entry::Occupied(mut entry) => {
@@ -53,9 +55,22 @@ fn memory_game(numbers: &[u64], num: usize) -> u64 {
     })
 }
 
-pub fn run() {
-    println!("{}", memory_game(&[15, 12, 0, 14, 3, 1], 2020));
-    println!("{}", memory_game(&[15, 12, 0, 14, 3, 1], 30000000));
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::list(input.trim(), ",", |number| parse::number(input, number))
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        memory_game(input, 2020).into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(memory_game(input, 30000000).into())
+    }
 }
 
 #[cfg(test)]
@@ -72,11 +87,23 @@ mod tests {
         (&[3,2,1], 438),
         (&[3,1,2], 1836),
     })]
-    #[test]
     fn test_memory_game_2020(case: (&[u64], u64)) {
         assert_eq!(memory_game(case.0, 2020), case.1)
     }
 
+    #[parameterized(case = {
+        ("0,3,6\n", Ok(vec![0, 3, 6])),
+        ("0,3,x", Err("column 5: invalid number 'x'")),
+        ("0, 3", Err("column 3: invalid number ' 3'")),
+        ("", Err("column 1: invalid number ''")),
+    })]
+    fn parse_numbers(case: (&str, Result<Vec<u64>, &str>)) {
+        assert_eq!(
+            Day15::parse(case.0).map_err(|error| error.to_string()),
+            case.1.map_err(str::to_string)
+        );
+    }
+
     // Too slow, disable for now
     // #[parameterized(case = {
     //     (&[0,3,6], 175594),
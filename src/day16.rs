@@ -1,9 +1,31 @@
-use crate::maybe_from::MaybeFrom;
+use crate::parse::{self, ParseError};
+use crate::render;
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
-use std::iter::FromIterator;
+use rand::rngs::StdRng;
+use rand::seq::{index, SliceRandom};
+use rand::Rng;
+use std::convert::TryFrom;
+use std::fmt;
 
-#[derive(Debug, Copy, Clone)]
+const FIELDS: &[&str] = &[
+    "arrival location",
+    "arrival station",
+    "class",
+    "departure platform",
+    "departure time",
+    "duration",
+    "price",
+    "route",
+    "row",
+    "seat",
+    "train",
+    "type",
+    "wagon",
+    "zone",
+];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 struct Range {
     inclusive_low: usize,
     inclusive_high: usize,
@@ -15,19 +37,23 @@ impl Range {
     }
 }
 
-impl MaybeFrom<&str> for Range {
-    fn maybe_from(value: &str) -> Option<Self> {
-        let (start, end) = value.split_once("-")?;
-        let inclusive_low = start.parse::<usize>().ok()?;
-        let inclusive_high = end.parse::<usize>().ok()?;
-        Some(Self {
-            inclusive_low,
-            inclusive_high,
+impl Range {
+    fn parse(source: &str, value: &str) -> Result<Self, ParseError> {
+        let (start, end) = parse::split(source, value, "-")?;
+        Ok(Self {
+            inclusive_low: parse::number(source, start)?,
+            inclusive_high: parse::number(source, end)?,
         })
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.inclusive_low, self.inclusive_high)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 struct Rule {
     low_range: Range,
     high_range: Range,
@@ -39,35 +65,45 @@ impl Rule {
     }
 }
 
-impl MaybeFrom<&str> for Rule {
-    fn maybe_from(value: &str) -> Option<Self> {
-        let (lhs, rhs) = value.split_once(" or ")?;
-        let low_range = Range::maybe_from(lhs)?;
-        let high_range = Range::maybe_from(rhs)?;
-        Some(Self {
-            low_range,
-            high_range,
+impl Rule {
+    fn parse(source: &str, value: &str) -> Result<Self, ParseError> {
+        let (lhs, rhs) = parse::split(source, value, " or ")?;
+        Ok(Self {
+            low_range: Range::parse(source, lhs)?,
+            high_range: Range::parse(source, rhs)?,
         })
     }
 }
 
-#[derive(Debug)]
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} or {}", self.low_range, self.high_range)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
 struct Field {
     name: String,
     rule: Rule,
 }
 
-impl MaybeFrom<&str> for Field {
-    fn maybe_from(value: &str) -> Option<Self> {
-        let (name, rules) = value.split_once(": ")?;
-        Rule::maybe_from(rules).map(|rule| Self {
+impl Field {
+    fn parse(source: &str, value: &str) -> Result<Self, ParseError> {
+        let (name, rule) = parse::split(source, value, ": ")?;
+        Ok(Self {
             name: name.to_string(),
-            rule,
+            rule: Rule::parse(source, rule)?,
         })
     }
 }
 
-#[derive(Debug)]
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.rule)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
 struct Ticket {
     numbers: Vec<usize>,
 }
@@ -81,16 +117,22 @@ impl Ticket {
     }
 }
 
-impl From<&str> for Ticket {
-    fn from(value: &str) -> Self {
-        Self {
-            numbers: value.split(",").flat_map(|num| num.parse().ok()).collect(),
-        }
+impl Ticket {
+    fn parse(source: &str, value: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            numbers: parse::list(value, ",", |num| parse::number(source, num))?,
+        })
+    }
+}
+
+impl fmt::Display for Ticket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.numbers.iter().join(","))
     }
 }
 
-#[derive(Debug)]
-struct Notes {
+#[derive(Debug, Eq, PartialEq)]
+pub struct Notes {
     fields: Vec<Field>,
     your_ticket: Ticket,
     nearby_tickets: Vec<Ticket>,
@@ -133,38 +175,133 @@ impl Notes {
     // }
 }
 
-fn parse(input: &str) -> Option<Notes> {
-    let (fields, tickets) = input.split_once("\n\nyour ticket:\n")?;
-    let fields = fields
-        .lines()
-        .flat_map(|line| Field::maybe_from(line))
-        .collect_vec();
-    let (your_ticket, nearby_tickets) = tickets.split_once("\n\nnearby tickets:\n")?;
-    let your_ticket = Ticket::from(your_ticket);
-    let nearby_tickets = nearby_tickets
-        .lines()
-        .map(|line| Ticket::from(line))
+enum Section {
+    Fields(Vec<Field>),
+    YourTicket(Ticket),
+    NearbyTickets(Vec<Ticket>),
+}
+
+impl Section {
+    fn parse(block: &str) -> Result<Self, ParseError> {
+        if let Some(ticket) = block.strip_prefix("your ticket:\n") {
+            Ok(Section::YourTicket(Ticket::parse(block, ticket)?))
+        } else if let Some(tickets) = block.strip_prefix("nearby tickets:\n") {
+            parse::list(tickets, "\n", |ticket| Ticket::parse(block, ticket))
+                .map(Section::NearbyTickets)
+        } else {
+            parse::list(block, "\n", |field| Field::parse(block, field)).map(Section::Fields)
+        }
+    }
+}
+
+impl TryFrom<&str> for Notes {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let sections = parse::blocks(value, Section::parse)?;
+        match <[Section; 3]>::try_from(sections) {
+            Ok(
+                [Section::Fields(fields), Section::YourTicket(your_ticket), Section::NearbyTickets(nearby_tickets)],
+            ) => Ok(Notes {
+                fields,
+                your_ticket,
+                nearby_tickets,
+            }),
+            _ => Err(ParseError::new(
+                "expected fields, your ticket and nearby tickets",
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Notes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\nyour ticket:\n{}\n\nnearby tickets:\n{}",
+            render::lines(&self.fields),
+            self.your_ticket,
+            render::lines(&self.nearby_tickets)
+        )
+    }
+}
+
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let count = rng.gen_range(3..=8);
+    let fields = FIELDS
+        .choose_multiple(rng, count)
+        .map(|name| {
+            let mut bounds = index::sample(rng, 999, 4).into_vec();
+            bounds.sort_unstable();
+            Field {
+                name: name.to_string(),
+                rule: Rule {
+                    low_range: Range {
+                        inclusive_low: bounds[0] + 1,
+                        inclusive_high: bounds[1] + 1,
+                    },
+                    high_range: Range {
+                        inclusive_low: bounds[2] + 1,
+                        inclusive_high: bounds[3] + 1,
+                    },
+                },
+            }
+        })
         .collect_vec();
-    Some(Notes {
+    let mut ticket = || Ticket {
+        numbers: (0..fields.len()).map(|_| rng.gen_range(0..=999)).collect(),
+    };
+    Notes {
+        your_ticket: ticket(),
+        nearby_tickets: (0..size).map(|_| ticket()).collect(),
         fields,
-        your_ticket,
-        nearby_tickets,
-    })
+    }
+    .to_string()
 }
 
-pub fn run() {
-    //     let input = "class: 1-3 or 5-7
-    // row: 6-11 or 33-44
-    // seat: 13-40 or 45-50
-    //
-    // your ticket:
-    // 7,1,14
-    //
-    // nearby tickets:
-    // 7,3,47
-    // 40,4,50
-    // 55,2,20
-    // 38,6,12";
-    let notes = parse(include_str!("data/16/1")).expect("failed to parse input");
-    println!("{}", notes.error_rate());
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Notes;
+
+    const PARTS: u8 = 1;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Notes::try_from(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.error_rate().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+    use rand::SeedableRng;
+
+    #[parameterized(case = {
+        ("class: 1-3 or 5-7\n\nyour ticket:\n7,1\n\nnearby tickets:\n7,3\n", Ok(2)),
+        ("class: 1-3 or 5-x\n\nyour ticket:\n7\n\nnearby tickets:\n7\n", Err("line 1, column 17: invalid number 'x'")),
+        ("class: 1-3 or 5-7\n\nyour ticket:\n7\n\nnearby tickets:\n7\n4,y\n", Err("line 8, column 3: invalid number 'y'")),
+        ("class: 1-3\n\nyour ticket:\n7\n\nnearby tickets:\n7\n", Err("line 1, column 8: expected ' or '")),
+        ("class: 1-3 or 5-7\n\nnearby tickets:\n7\n", Err("expected fields, your ticket and nearby tickets")),
+    })]
+    fn parse_notes(case: (&str, Result<usize, &str>)) {
+        assert_eq!(
+            Notes::try_from(case.0)
+                .map(|notes| notes.nearby_tickets.iter().map(|t| t.numbers.len()).sum())
+                .map_err(|error| error.to_string()),
+            case.1.map_err(|error| error.to_string())
+        );
+    }
+
+    #[parameterized(seed = { 0, 1, 2, 3, 4 })]
+    fn round_trip(seed: u64) {
+        let input = generate(&mut StdRng::seed_from_u64(seed), 30);
+        let notes = Notes::try_from(input.as_str()).unwrap();
+        assert_eq!(notes.to_string(), input);
+        assert_eq!(Notes::try_from(notes.to_string().as_str()), Ok(notes));
+    }
 }
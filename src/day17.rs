@@ -1,51 +1,16 @@
 use crate::maybe_from::MaybeFrom;
+use crate::nd::{Extent, PointN};
+use crate::parse::ParseError;
+use crate::solution::{Answer, Solution};
+use crate::trace::{self, Event};
+use crate::twod;
 use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
+use std::convert::TryFrom;
 use std::fmt;
 use std::iter::FromIterator;
 
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
-struct Coordinate {
-    x: i32,
-    y: i32,
-    z: i32,
-}
-
-impl Coordinate {
-    fn new(x: i32, y: i32, z: i32) -> Self {
-        Self { x, y, z }
-    }
-    fn neighbors(&self) -> [Coordinate; 26] {
-        [
-            Coordinate::new(self.x, self.y - 1, self.z),
-            Coordinate::new(self.x, self.y - 1, self.z - 1),
-            Coordinate::new(self.x, self.y - 1, self.z + 1),
-            Coordinate::new(self.x, self.y + 1, self.z),
-            Coordinate::new(self.x, self.y + 1, self.z - 1),
-            Coordinate::new(self.x, self.y + 1, self.z + 1),
-            Coordinate::new(self.x, self.y, self.z - 1),
-            Coordinate::new(self.x, self.y, self.z + 1),
-            Coordinate::new(self.x + 1, self.y - 1, self.z),
-            Coordinate::new(self.x + 1, self.y - 1, self.z - 1),
-            Coordinate::new(self.x + 1, self.y - 1, self.z + 1),
-            Coordinate::new(self.x + 1, self.y + 1, self.z),
-            Coordinate::new(self.x + 1, self.y + 1, self.z - 1),
-            Coordinate::new(self.x + 1, self.y + 1, self.z + 1),
-            Coordinate::new(self.x + 1, self.y, self.z),
-            Coordinate::new(self.x + 1, self.y, self.z - 1),
-            Coordinate::new(self.x + 1, self.y, self.z + 1),
-            Coordinate::new(self.x - 1, self.y - 1, self.z),
-            Coordinate::new(self.x - 1, self.y - 1, self.z - 1),
-            Coordinate::new(self.x - 1, self.y - 1, self.z + 1),
-            Coordinate::new(self.x - 1, self.y + 1, self.z),
-            Coordinate::new(self.x - 1, self.y + 1, self.z - 1),
-            Coordinate::new(self.x - 1, self.y + 1, self.z + 1),
-            Coordinate::new(self.x - 1, self.y, self.z),
-            Coordinate::new(self.x - 1, self.y, self.z - 1),
-            Coordinate::new(self.x - 1, self.y, self.z + 1),
-        ]
-    }
-}
+const AXES: [&str; 4] = ["x", "y", "z", "w"];
 
 #[derive(Debug)]
 enum CubeState {
@@ -54,15 +19,24 @@ enum CubeState {
 }
 
 #[derive(Debug, Clone)]
-struct Universe {
-    active_cubes: HashSet<Coordinate>,
+pub struct Universe<const D: usize> {
+    active_cubes: HashSet<PointN<D>>,
 }
 
-impl Universe {
-    fn cube_state(&self, coord: &Coordinate) -> CubeState {
+impl<const D: usize> Universe<D> {
+    fn embed<const E: usize>(&self) -> Universe<E> {
+        Universe {
+            active_cubes: HashSet::from_iter(
+                self.active_cubes
+                    .iter()
+                    .map(|cube| PointN::embed(cube.coords())),
+            ),
+        }
+    }
+
+    fn cube_state(&self, coord: &PointN<D>) -> CubeState {
         let active_neighbors = coord
             .neighbors()
-            .iter()
             .filter(|coord| self.active_cubes.contains(coord))
             .count();
         if self.active_cubes.contains(coord) {
@@ -72,114 +46,114 @@ impl Universe {
         }
     }
 
-    fn extent(&self) -> ((i32, i32), (i32, i32), (i32, i32)) {
-        self.active_cubes.iter().fold(
-            ((0, 0), (0, 0), (0, 0)),
-            |((min_x, max_x), (min_y, max_y), (min_z, max_z)), coord| {
-                (
-                    (
-                        if coord.x < min_x { coord.x } else { min_x },
-                        if coord.x > max_x { coord.x } else { max_x },
-                    ),
-                    (
-                        if coord.y < min_y { coord.y } else { min_y },
-                        if coord.y > max_y { coord.y } else { max_y },
-                    ),
-                    (
-                        if coord.z < min_z { coord.z } else { min_z },
-                        if coord.z > max_z { coord.z } else { max_z },
-                    ),
-                )
-            },
-        )
-    }
-
-    fn evolve(&self) -> Universe {
-        let ((min_x, max_x), (min_y, max_y), (min_z, max_z)) = self.extent();
+    fn evolve(&self) -> Universe<D> {
+        let extent = match Extent::of(&self.active_cubes) {
+            Some(extent) => extent.grow(1),
+            None => return self.clone(),
+        };
         Self {
-            active_cubes: HashSet::from_iter(
-                (min_x - 1..=max_x + 1)
-                    .flat_map(move |x| {
-                        (min_y - 1..=max_y + 1).flat_map(move |y| {
-                            (min_z - 1..=max_z + 1).map(move |z| Coordinate::new(x, z, y))
-                        })
-                    })
-                    .flat_map(|coord| match self.cube_state(&coord) {
-                        CubeState::Active { active_neighbors } => {
-                            if active_neighbors == 2 || active_neighbors == 3 {
-                                Some(coord)
-                            } else {
-                                None
-                            }
-                        }
-                        CubeState::Inactive { active_neighbors } => {
-                            if active_neighbors == 3 {
-                                Some(coord)
-                            } else {
-                                None
-                            }
-                        }
-                    }),
-            ),
+            active_cubes: HashSet::from_iter(extent.points().filter(|coord| {
+                matches!(
+                    self.cube_state(coord),
+                    CubeState::Active {
+                        active_neighbors: 2..=3
+                    } | CubeState::Inactive {
+                        active_neighbors: 3
+                    }
+                )
+            })),
         }
     }
 }
 
-impl fmt::Display for Universe {
+impl<const D: usize> fmt::Display for Universe<D> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let ((min_x, max_x), (min_y, max_y), (min_z, max_z)) = self.extent();
-        for z in (min_z..=max_z) {
-            write!(f, "z={}\n", z)?;
-            for y in (min_y..=max_y) {
-                write!(
-                    f,
-                    "{}\n",
-                    (min_x..=max_x)
-                        .map(
-                            |x| if self.active_cubes.contains(&Coordinate::new(x, y, z)) {
-                                '#'
-                            } else {
-                                '.'
-                            }
-                        )
-                        .collect::<String>()
-                );
+        let extent = match Extent::of(&self.active_cubes) {
+            Some(extent) => extent,
+            None => return Ok(()),
+        };
+        let slices = extent
+            .points()
+            .filter(|point| point[0] == extent.min[0] && point[1] == extent.min[1]);
+        for slice in slices {
+            if D > 2 {
+                let label = (2..D)
+                    .map(|axis| match AXES.get(axis) {
+                        Some(name) => format!("{}={}", name, slice[axis]),
+                        None => format!("d{}={}", axis, slice[axis]),
+                    })
+                    .join(", ");
+                writeln!(f, "{}", label)?;
+            }
+            for y in 0..extent.len(1) as i32 {
+                let row = (0..extent.len(0) as i32)
+                    .map(|x| {
+                        let mut coords = *slice.coords();
+                        coords[0] += x;
+                        coords[1] += y;
+                        match self.active_cubes.contains(&PointN::new(coords)) {
+                            true => '#',
+                            false => '.',
+                        }
+                    })
+                    .collect::<String>();
+                writeln!(f, "{}", row)?;
             }
         }
         Ok(())
     }
 }
 
-impl From<&str> for Universe {
-    fn from(value: &str) -> Self {
-        Self {
-            active_cubes: HashSet::from_iter(value.lines().enumerate().flat_map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .filter(|(_, c)| *c == '#')
-                    .map(move |(x, _)| Coordinate::new(x as i32, y as i32, 0))
-            })),
-        }
+#[derive(Debug, Copy, Clone, MaybeFrom)]
+enum Cube {
+    #[token('#')]
+    Active,
+}
+
+impl TryFrom<&str> for Universe<2> {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let cubes = twod::parse_points::<Cube>(value, '.')?;
+        Ok(Self {
+            active_cubes: HashSet::from_iter(
+                cubes
+                    .keys()
+                    .map(|point| PointN::new([point.x as i32, point.y as i32])),
+            ),
+        })
     }
 }
 
-pub fn run() {
-    let real_input = "...#.#.#
-..#..#..
-#.#.##.#
-###.##..
-#####.##
-#.......
-#..#..##
-...##.##";
-    let input = ".#.
-..#
-###";
-    let universe = Universe::from(input);
-    let result = (0..6).fold(universe.clone(), |universe, evolution| {
-        println!("evolution={}\n{}", evolution, universe);
+fn boot<const D: usize>(input: &Universe<2>) -> usize {
+    let result = (0..6).fold(input.embed::<D>(), |universe, evolution| {
+        trace::emit(Event::Step {
+            step: evolution,
+            state: &universe,
+        });
         universe.evolve()
     });
-    println!("evolution=6\n{}", result);
-    println!("{}", result.active_cubes.len());
+    trace::emit(Event::Step {
+        step: 6,
+        state: &result,
+    });
+    result.active_cubes.len()
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Universe<2>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Universe::try_from(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        boot::<3>(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(boot::<4>(input).into())
+    }
 }
@@ -1,43 +1,67 @@
 use crate::maybe_from::MaybeFrom;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::Rng;
 
-#[derive(Debug, Copy, Clone)]
-enum Token {
+#[derive(Debug, Copy, Clone, MaybeFrom)]
+pub enum Token {
+    #[token(parse)]
     Num(u64),
+    #[token("+")]
     Add,
+    #[token("*")]
     Mul,
+    #[token("(")]
     OpenParen,
+    #[token(")")]
     ClosingParen,
 }
 
-impl Token {
-    fn get_num(&self) -> Option<u64> {
-        match self {
-            Token::Num(num) => Some(*num),
-            _ => None,
-        }
-    }
-}
+const EXPECTED_OPERAND: &str = "expected a number or '('";
 
-impl MaybeFrom<&str> for Token {
-    fn maybe_from(value: &str) -> Option<Self> {
-        use Token::*;
-        match value {
-            "+" => Some(Add),
-            "*" => Some(Mul),
-            "(" => Some(OpenParen),
-            ")" => Some(ClosingParen),
-            num => num.parse::<u64>().ok().map(|num| Num(num)),
+fn tokenize(expr: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut open = Vec::new();
+    let mut operand = true;
+    let mut rest = expr.trim_start();
+    while let Some(first) = rest.chars().next() {
+        let end = match rest.find(|c: char| !c.is_ascii_digit()) {
+            Some(0) => first.len_utf8(),
+            Some(end) => end,
+            None => rest.len(),
+        };
+        let part = &rest[..end];
+        let token = parse::token(expr, part, "token")?;
+        match token {
+            Token::Num(_) | Token::OpenParen if !operand => {
+                return Err(parse::error_at(expr, part, "expected an operator or ')'"))
+            }
+            Token::Add | Token::Mul | Token::ClosingParen if operand => {
+                return Err(parse::error_at(expr, part, EXPECTED_OPERAND))
+            }
+            Token::OpenParen => open.push(part),
+            Token::ClosingParen if open.pop().is_none() => {
+                return Err(parse::error_at(expr, part, "unmatched ')'"))
+            }
+            _ => (),
         }
+        operand = matches!(token, Token::Add | Token::Mul | Token::OpenParen);
+        tokens.push(token);
+        rest = rest[end..].trim_start();
+    }
+    if operand {
+        return Err(parse::error_at(
+            expr,
+            &expr[expr.trim_end().len()..],
+            EXPECTED_OPERAND,
+        ));
+    }
+    match open.pop() {
+        Some(paren) => Err(parse::error_at(expr, paren, "unmatched '('")),
+        None => Ok(tokens),
     }
-}
-
-fn tokenize(expr: &str) -> Vec<Token> {
-    expr.replace("(", " ( ")
-        .replace(")", " ) ")
-        .split(" ")
-        .flat_map(Token::maybe_from)
-        .collect()
 }
 
 #[derive(Debug, Clone)]
@@ -122,7 +146,7 @@ fn deparen(tokens: Vec<Token>) -> Vec<Expr> {
                             }
                             _ => (),
                         }
-                        inner.push(token.clone());
+                        inner.push(*token);
                     }
                     skip = count;
                     Expr::Value(Value::Expr(deparen(inner)))
@@ -148,7 +172,7 @@ fn evaluate(exprs: &[Expr]) -> u64 {
         n if n > 3 => {
             let value = evaluate_triple([&exprs[0], &exprs[1], &exprs[2]]);
             evaluate(
-                &vec![Expr::Value(Value::Const(value))]
+                &[Expr::Value(Value::Const(value))]
                     .iter()
                     .chain(exprs.iter().skip(3))
                     .cloned()
@@ -159,20 +183,50 @@ fn evaluate(exprs: &[Expr]) -> u64 {
     }
 }
 
-fn math(expr: &str) -> u64 {
-    let tokens = tokenize(expr);
-    let exprs = deparen(tokens);
-    evaluate(&exprs)
+fn math(tokens: &[Token]) -> u64 {
+    evaluate(&deparen(tokens.to_vec()))
 }
 
-pub fn run() {
-    println!(
-        "{}",
-        include_str!("data/18/1")
-            .lines()
-            .map(|line| math(line))
-            .sum::<u64>()
-    );
+fn expression(rng: &mut StdRng, budget: &mut usize, depth: usize) -> String {
+    let operands = rng.gen_range(2..=6);
+    let mut expr = String::new();
+    for index in 0..operands {
+        if index > 0 {
+            expr += if rng.gen_bool(0.5) { " + " } else { " * " };
+        }
+        if *budget > 2 && depth < 3 && rng.gen_bool(0.25) {
+            expr += &format!("({})", expression(rng, budget, depth + 1));
+        } else {
+            *budget -= 1;
+            expr += &rng.gen_range(1..=9).to_string();
+        }
+        if *budget == 0 {
+            break;
+        }
+    }
+    expr
+}
+
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| expression(rng, &mut 12, 0) + "\n")
+        .collect()
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<Vec<Token>>;
+
+    const PARTS: u8 = 1;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::lines(input, tokenize)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.iter().map(|tokens| math(tokens)).sum::<u64>().into()
+    }
 }
 
 #[cfg(test)]
@@ -188,8 +242,27 @@ mod tests {
         ("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", 12240),
         ("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2", 13632)
     })]
-    #[test]
     fn test_math(case: (&str, u64)) {
-        assert_eq!(math(case.0), case.1)
+        assert_eq!(math(&tokenize(case.0).unwrap()), case.1)
+    }
+
+    #[parameterized(case = {
+        ("1 + 2\n3 - 4", "line 2, column 3: unknown token '-'"),
+        ("(1 + x)", "line 1, column 6: unknown token 'x'"),
+        ("2 * 3 + 1\n\n4 / 2\n", "line 3, column 3: unknown token '/'"),
+        ("2 * 3 +\n", "line 1, column 8: expected a number or '('"),
+        ("1 + 2)", "line 1, column 6: unmatched ')'"),
+        ("1 2", "line 1, column 3: expected an operator or ')'"),
+        ("(1 + (2 * 3)", "line 1, column 1: unmatched '('"),
+        ("4 + ()", "line 1, column 6: expected a number or '('"),
+        ("* 2", "line 1, column 1: expected a number or '('"),
+    })]
+    fn invalid_expression(case: (&str, &str)) {
+        assert_eq!(
+            Day18::parse(case.0)
+                .map(|_| ())
+                .map_err(|error| error.to_string()),
+            Err(case.1.to_string())
+        );
     }
 }
@@ -1,7 +1,11 @@
-use crate::maybe_from::MaybeFrom;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::convert::TryFrom;
 
 #[derive(Debug)]
-struct Entry {
+pub struct Entry {
     letter: char,
     low: usize,
     high: usize,
@@ -16,24 +20,27 @@ impl Entry {
 
     fn valid_toboggan(&self) -> bool {
         let chars: Vec<char> = self.password.chars().collect();
-        chars.get(self.low - 1).map_or(false, |&c| c == self.letter)
-            ^ chars
-                .get(self.high - 1)
-                .map_or(false, |&c| c == self.letter)
+        chars.get(self.low - 1).is_some_and(|&c| c == self.letter)
+            ^ chars.get(self.high - 1).is_some_and(|&c| c == self.letter)
     }
 }
 
-impl MaybeFrom<&str> for Entry {
-    fn maybe_from(value: &str) -> Option<Self> {
-        let (rule, password) = value.split_once(": ")?;
-        let (nums, letter) = value.split_once(" ")?;
-        let (low, high) = nums.split_once("-")?;
-        let low: usize = low.parse().ok()?;
-        let high: usize = high.parse().ok()?;
-        Some(Entry {
-            letter: letter.chars().next()?,
-            low,
-            high,
+impl TryFrom<&str> for Entry {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (rule, password) = parse::split(value, value, ": ")?;
+        let (nums, letter) = parse::split(value, rule, " ")?;
+        let (low, high) = parse::split(value, nums, "-")?;
+        let mut letters = letter.chars();
+        let letter = match (letters.next(), letters.next()) {
+            (Some(letter), None) => letter,
+            _ => return Err(parse::error_at(value, letter, "expected a single letter")),
+        };
+        Ok(Entry {
+            letter,
+            low: parse::number(value, low)?,
+            high: parse::number(value, high)?,
             password: password.to_owned(),
         })
     }
@@ -50,16 +57,37 @@ fn count_valid_entries_toboggan(entries: &[Entry]) -> usize {
         .count()
 }
 
-fn get_entries(input: &str) -> Vec<Entry> {
-    input
-        .lines()
-        .flat_map(|line| Entry::maybe_from(line))
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let letter = rng.gen_range(b'a'..=b'z') as char;
+            let low = rng.gen_range(1..=10);
+            let high = rng.gen_range(low..=20);
+            let password: String = (0..rng.gen_range(1..=20))
+                .map(|_| match rng.gen_bool(0.4) {
+                    true => letter,
+                    false => rng.gen_range(b'a'..=b'z') as char,
+                })
+                .collect();
+            format!("{}-{} {}: {}\n", low, high, letter, password)
+        })
         .collect()
 }
 
-pub fn run() {
-    let input = include_str!("data/2/1");
-    let entries = get_entries(input);
-    println!("{}", count_valid_entries_sled(&entries));
-    println!("{}", count_valid_entries_toboggan(&entries));
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Entry>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::lines(input, |line| Entry::try_from(line))
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        count_valid_entries_sled(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(count_valid_entries_toboggan(input).into())
+    }
 }
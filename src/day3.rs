@@ -1,11 +1,14 @@
 use crate::maybe_from::MaybeFrom;
-use crate::twod::{Point, Vector};
-use std::fmt::{Display, Formatter, Result as FmtResult};
-use std::ops::{Add, AddAssign};
+use crate::parse::ParseError;
+use crate::solution::{Answer, Solution};
+use crate::twod::{self, Grid, Point, Vector};
+use std::convert::TryFrom;
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, MaybeFrom)]
 enum Square {
+    #[token('.')]
     Open,
+    #[token('#')]
     Tree,
 }
 
@@ -18,52 +21,21 @@ impl Square {
     }
 }
 
-impl MaybeFrom<char> for Square {
-    fn maybe_from(value: char) -> Option<Self> {
-        match value {
-            '.' => Some(Square::Open),
-            '#' => Some(Square::Tree),
-            _ => None,
-        }
-    }
-}
-
-struct Row {
-    squares: Vec<Square>,
-}
-
-impl Row {
-    fn get(&self, index: usize) -> Square {
-        self.squares
-            .get(index % self.squares.len())
-            .unwrap()
-            .clone()
-    }
-}
-
-impl From<&str> for Row {
-    fn from(value: &str) -> Self {
-        Row {
-            squares: value.chars().flat_map(|c| Square::maybe_from(c)).collect(),
-        }
-    }
-}
-
-struct Map {
-    rows: Vec<Row>,
+pub struct Map {
+    grid: Grid<Square>,
 }
 
 impl Map {
-    fn run(&self, vector: Vector<usize>) -> Toboggan {
-        Toboggan::new(&self, vector)
+    fn run(&self, vector: Vector<usize>) -> Toboggan<'_> {
+        Toboggan::new(self, vector)
     }
 }
 
-impl From<&str> for Map {
-    fn from(value: &str) -> Self {
-        Map {
-            rows: value.lines().map(|line| Row::from(line)).collect(),
-        }
+impl TryFrom<&str> for Map {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        twod::parse_grid(value).map(|grid| Map { grid })
     }
 }
 
@@ -87,20 +59,36 @@ impl<'a> Iterator for Toboggan<'a> {
     type Item = Square;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let result = self.map.rows.get(self.pos.y).map(|row| row.get(self.pos.x));
+        let grid = &self.map.grid;
+        let result = grid
+            .get(&Point::new(self.pos.x % grid.width().max(1), self.pos.y))
+            .cloned();
         self.pos += self.vec;
         result
     }
 }
 
-pub fn run() {
-    let input = include_str!("data/3/1");
-    let map = Map::from(input);
-    println!("{}", Square::Tree.count(map.run((3, 1).into())));
-    let slopes: [(usize, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
-    let result: usize = slopes
-        .iter()
-        .map(|slope| Square::Tree.count(map.run(slope.into())))
-        .fold(1, |a, b| a * b);
-    println!("{}", result);
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Map;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Map::try_from(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        Square::Tree.count(input.run((3, 1).into())).into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        let slopes: [(usize, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+        Some(
+            slopes
+                .iter()
+                .map(|slope| Square::Tree.count(input.run(slope.into())))
+                .product::<usize>()
+                .into(),
+        )
+    }
 }
@@ -1,5 +1,7 @@
-use crate::day4::ValidatedData::Valid;
 use crate::maybe_from::MaybeFrom;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::trace::{self, Event};
 use lazy_static::lazy_static;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
@@ -19,38 +21,33 @@ lazy_static! {
     };
 }
 
+// The parsed values are only read through Debug, in rejection traces.
+#[allow(dead_code)]
 #[derive(Debug)]
 enum Height {
     Centimeters(i32),
     Inches(i32),
 }
 
-#[derive(Debug)]
+#[derive(Debug, MaybeFrom)]
 enum EyeColor {
+    #[token("amb")]
     Amber,
+    #[token("blu")]
     Blue,
+    #[token("brn")]
     Brown,
+    #[token("gry")]
     Gray,
+    #[token("grn")]
     Green,
+    #[token("hzl")]
     Hazel,
+    #[token("oth")]
     Other,
 }
 
-impl MaybeFrom<&str> for EyeColor {
-    fn maybe_from(value: &str) -> Option<Self> {
-        match value {
-            "amb" => Some(EyeColor::Amber),
-            "blu" => Some(EyeColor::Blue),
-            "brn" => Some(EyeColor::Brown),
-            "gry" => Some(EyeColor::Gray),
-            "grn" => Some(EyeColor::Green),
-            "hzl" => Some(EyeColor::Hazel),
-            "oth" => Some(EyeColor::Other),
-            _ => None,
-        }
-    }
-}
-
+#[allow(dead_code)]
 #[derive(Debug)]
 enum ValidatedData<T: Debug> {
     Valid(T),
@@ -71,14 +68,8 @@ impl ValidatedData<i32> {
         value
             .parse::<i32>()
             .ok()
-            .and_then(|year| {
-                if year >= 1920 && year <= 2002 {
-                    Some(year)
-                } else {
-                    None
-                }
-            })
-            .map(|year| ValidatedData::Valid(year))
+            .filter(|&year| (1920..=2002).contains(&year))
+            .map(ValidatedData::Valid)
             .unwrap_or_else(|| ValidatedData::Invalid(value.to_string()))
     }
 
@@ -86,14 +77,8 @@ impl ValidatedData<i32> {
         value
             .parse::<i32>()
             .ok()
-            .and_then(|year| {
-                if year >= 2010 && year <= 2020 {
-                    Some(year)
-                } else {
-                    None
-                }
-            })
-            .map(|year| ValidatedData::Valid(year))
+            .filter(|&year| (2010..=2020).contains(&year))
+            .map(ValidatedData::Valid)
             .unwrap_or_else(|| ValidatedData::Invalid(value.to_string()))
     }
 
@@ -101,14 +86,8 @@ impl ValidatedData<i32> {
         value
             .parse::<i32>()
             .ok()
-            .and_then(|year| {
-                if year >= 2020 && year <= 2030 {
-                    Some(year)
-                } else {
-                    None
-                }
-            })
-            .map(|year| ValidatedData::Valid(year))
+            .filter(|&year| (2020..=2030).contains(&year))
+            .map(ValidatedData::Valid)
             .unwrap_or_else(|| ValidatedData::Invalid(value.to_string()))
     }
 }
@@ -122,13 +101,7 @@ impl ValidatedData<Height> {
                 .collect::<String>()
                 .parse::<i32>()
                 .ok()
-                .and_then(|num| {
-                    if num >= 150 && num <= 193 {
-                        Some(num)
-                    } else {
-                        None
-                    }
-                })
+                .filter(|&num| (150..=193).contains(&num))
                 .map(|num| ValidatedData::Valid(Height::Centimeters(num)))
                 .unwrap_or_else(|| ValidatedData::Invalid(value.to_string()))
         } else if value.ends_with("in") {
@@ -138,13 +111,7 @@ impl ValidatedData<Height> {
                 .collect::<String>()
                 .parse::<i32>()
                 .ok()
-                .and_then(|num| {
-                    if num >= 59 && num <= 76 {
-                        Some(num)
-                    } else {
-                        None
-                    }
-                })
+                .filter(|&num| (59..=76).contains(&num))
                 .map(|num| ValidatedData::Valid(Height::Inches(num)))
                 .unwrap_or_else(|| ValidatedData::Invalid(value.to_string()))
         } else {
@@ -182,19 +149,20 @@ impl ValidatedData<String> {
 impl ValidatedData<EyeColor> {
     fn ecl(value: &str) -> ValidatedData<EyeColor> {
         EyeColor::maybe_from(value)
-            .map(|ec| ValidatedData::Valid(ec))
+            .map(ValidatedData::Valid)
             .unwrap_or_else(|| ValidatedData::Invalid(value.to_string()))
     }
 }
 
 #[derive(Debug)]
-struct Passport {
+pub struct Passport {
     ecl: ValidatedData<EyeColor>,
     pid: ValidatedData<String>,
     eyr: ValidatedData<i32>,
     hcl: ValidatedData<String>,
     byr: ValidatedData<i32>,
     iyr: ValidatedData<i32>,
+    #[allow(dead_code)]
     cid: Option<ValidatedData<String>>,
     hgt: ValidatedData<Height>,
 }
@@ -219,23 +187,18 @@ impl Passport {
     }
 }
 
-impl MaybeFrom<&str> for Passport {
-    fn maybe_from(value: &str) -> Option<Self> {
-        let mut fields: HashMap<&str, &str> = HashMap::new();
-        for part in value.split_whitespace() {
-            let (key, value) = part.split_once(":")?;
-            fields.insert(key, value);
-        }
-        if REQUIRED_FIELDS.is_subset(&HashSet::from_iter(fields.keys().map(|k| k.clone()))) {
+impl Passport {
+    fn from_fields(fields: HashMap<&str, &str>) -> Option<Self> {
+        if REQUIRED_FIELDS.is_subset(&HashSet::from_iter(fields.keys().copied())) {
             Some(Passport {
-                ecl: ValidatedData::ecl(fields.get("ecl").unwrap()),
-                pid: ValidatedData::pid(fields.get("pid").unwrap()),
-                eyr: ValidatedData::eyr(fields.get("eyr").unwrap()),
-                hcl: ValidatedData::hcl(fields.get("hcl").unwrap()),
-                byr: ValidatedData::byr(fields.get("byr").unwrap()),
-                iyr: ValidatedData::iyr(fields.get("iyr").unwrap()),
+                ecl: ValidatedData::ecl(fields["ecl"]),
+                pid: ValidatedData::pid(fields["pid"]),
+                eyr: ValidatedData::eyr(fields["eyr"]),
+                hcl: ValidatedData::hcl(fields["hcl"]),
+                byr: ValidatedData::byr(fields["byr"]),
+                iyr: ValidatedData::iyr(fields["iyr"]),
                 cid: fields.get("cid").map(|s| ValidatedData::cid(s)),
-                hgt: ValidatedData::hgt(fields.get("hgt").unwrap()),
+                hgt: ValidatedData::hgt(fields["hgt"]),
             })
         } else {
             None
@@ -243,68 +206,33 @@ impl MaybeFrom<&str> for Passport {
     }
 }
 
-pub fn run() {
-    let input = include_str!("data/4/1");
-    let passports: Vec<Passport> = input
-        .split("\n\n")
-        .flat_map(|part| Passport::maybe_from(part))
-        .collect();
-    println!("{}", passports.len());
-    println!("{}", passports.iter().filter(|p| p.is_valid()).count());
-    for passport in passports {
-        if !passport.is_valid() {
-            println!("{}: {:?}", passport.errors(), passport)
-        }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::day4::Passport;
-    use crate::maybe_from::MaybeFrom;
-
-    #[test]
-    fn valid_passports() {
-        let passports: Vec<Passport> = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
-hcl:#623a2f
-
-eyr:2029 ecl:blu cid:129 byr:1989
-iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+pub struct Day4;
 
-hcl:#888785
-hgt:164cm byr:2001 iyr:2015 cid:88
-pid:545766238 ecl:hzl
-eyr:2022
+impl Solution for Day4 {
+    type Input = Vec<Passport>;
 
-iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719
-"
-        .split("\n\n")
-        .flat_map(|part| Passport::maybe_from(part))
-        .collect();
-        assert_eq!(passports.len(), 4);
-        assert!(passports.iter().all(|passport| passport.is_valid()));
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(
+            parse::blocks(input, |block| parse::pairs(block, block, ":"))?
+                .into_iter()
+                .flat_map(Passport::from_fields)
+                .collect(),
+        )
     }
 
-    #[test]
-    fn invalid_passports() {
-        let passports: Vec<Passport> = "eyr:1972 cid:100
-hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
-
-iyr:2019
-hcl:#602927 eyr:1967 hgt:170cm
-ecl:grn pid:012533040 byr:1946
-
-hcl:dab227 iyr:2012
-ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+    fn part1(input: &Self::Input) -> Answer {
+        input.len().into()
+    }
 
-hgt:59cm ecl:zzz
-eyr:2038 hcl:74454a iyr:2023
-pid:3556412378 byr:2007
-"
-        .split("\n\n")
-        .flat_map(|part| Passport::maybe_from(part))
-        .collect();
-        assert_eq!(passports.len(), 4);
-        assert!(passports.iter().all(|passport| !passport.is_valid()));
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        for passport in input {
+            if !passport.is_valid() {
+                trace::emit(Event::Rejected {
+                    record: passport,
+                    reason: &format_args!("{} invalid fields", passport.errors()),
+                })
+            }
+        }
+        Some(input.iter().filter(|p| p.is_valid()).count().into())
     }
 }
@@ -1,7 +1,11 @@
-use crate::maybe_from::MaybeFrom;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
 use std::collections::HashSet;
-use std::iter::FromIterator;
+use std::convert::TryFrom;
 
 #[derive(Debug, Eq, PartialEq)]
 struct BoardingPass {
@@ -15,57 +19,106 @@ impl BoardingPass {
     }
 }
 
-impl MaybeFrom<&str> for BoardingPass {
-    fn maybe_from(value: &str) -> Option<Self> {
+fn partition(
+    value: &str,
+    skip: usize,
+    size: usize,
+    low: char,
+    high: char,
+) -> Result<usize, ParseError> {
+    value
+        .chars()
+        .enumerate()
+        .skip(skip)
+        .take(size)
+        .try_fold(
+            (0usize..1 << size).collect_vec(),
+            |bucket, (index, pos)| match pos {
+                pos if pos == low => {
+                    Ok(bucket.iter().cloned().take(bucket.len() / 2).collect_vec())
+                }
+                pos if pos == high => {
+                    Ok(bucket.iter().cloned().skip(bucket.len() / 2).collect_vec())
+                }
+                _ => Err(ParseError::at(
+                    index + 1,
+                    format!("expected '{}' or '{}', found '{}'", low, high, pos),
+                )),
+            },
+        )
+        .map(|bucket| bucket[0])
+}
+
+impl TryFrom<&str> for BoardingPass {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
         if value.chars().count() != 10 {
-            return None;
+            return Err(ParseError::at(1, "boarding pass must be 10 characters"));
         }
-        let row = value
-            .chars()
-            .take(7)
-            .try_fold((0usize..128).collect_vec(), |bucket, pos| match pos {
-                'F' => Some(bucket.iter().cloned().take(bucket.len() / 2).collect_vec()),
-                'B' => Some(bucket.iter().cloned().skip(bucket.len() / 2).collect_vec()),
-                _ => None,
-            })?
-            .first()?
-            .clone();
-        let col = value
-            .chars()
-            .skip(7)
-            .take(3)
-            .try_fold((0usize..8).collect_vec(), |bucket, pos| match pos {
-                'L' => Some(bucket.iter().cloned().take(bucket.len() / 2).collect_vec()),
-                'R' => Some(bucket.iter().cloned().skip(bucket.len() / 2).collect_vec()),
-                _ => None,
-            })?
-            .first()?
-            .clone();
-        Some(BoardingPass { col, row })
+        let row = partition(value, 0, 7, 'F', 'B')?;
+        let col = partition(value, 7, 3, 'L', 'R')?;
+        Ok(BoardingPass { col, row })
     }
 }
 
-pub fn run() {
-    let input = include_str!("data/5/1");
-    let boarding_passes = input
-        .lines()
-        .flat_map(|line| BoardingPass::maybe_from(line))
-        .collect_vec();
-    let seat_ids: HashSet<usize> =
-        HashSet::from_iter(boarding_passes.iter().map(|bp| bp.seat_id()));
-    println!("{}", seat_ids.iter().max().expect("no passes"));
-    let free_seat = (0usize..128)
-        .cartesian_product((0usize..8))
-        .map(|(row, col)| BoardingPass { row, col })
-        .filter(|bp| {
-            let seat_id = bp.seat_id();
-            !seat_ids.contains(&seat_id)
-                && seat_ids.contains(&(seat_id + 1))
-                && seat_ids.contains(&(seat_id - 1))
+fn encode(seat_id: usize) -> String {
+    (0..10)
+        .rev()
+        .zip("FFFFFFFLLL".chars().zip("BBBBBBBRRR".chars()))
+        .map(|(bit, (low, high))| match seat_id >> bit & 1 {
+            0 => low,
+            _ => high,
         })
-        .exactly_one()
-        .expect("more than one found");
-    println!("{:?}", free_seat.seat_id());
+        .collect()
+}
+
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let first = rng.gen_range(1..1023 - size);
+    let missing = rng.gen_range(first + 1..first + size);
+    let mut seats = (first..=first + size)
+        .filter(|&seat_id| seat_id != missing)
+        .collect_vec();
+    seats.shuffle(rng);
+    seats
+        .into_iter()
+        .map(|seat_id| encode(seat_id) + "\n")
+        .collect()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = HashSet<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse::lines(input, |line| BoardingPass::try_from(line))?
+            .iter()
+            .map(|bp| bp.seat_id())
+            .collect())
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        (*input.iter().max().expect("no passes")).into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(
+            (0usize..128)
+                .cartesian_product(0usize..8)
+                .map(|(row, col)| BoardingPass { row, col })
+                .filter(|bp| {
+                    let seat_id = bp.seat_id();
+                    !input.contains(&seat_id)
+                        && input.contains(&(seat_id + 1))
+                        && input.contains(&(seat_id - 1))
+                })
+                .exactly_one()
+                .expect("more than one found")
+                .seat_id()
+                .into(),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -83,7 +136,7 @@ mod tests {
         BoardingPass { row: 102, col: 4 }
     })]
     fn boarding_pass(input: &str, result: BoardingPass) {
-        assert_eq!(BoardingPass::maybe_from(input), Some(result));
+        assert_eq!(BoardingPass::try_from(input), Ok(result));
     }
 
     #[parameterized(input = {
@@ -95,7 +148,6 @@ mod tests {
         119,
         820
     })]
-    #[test]
     fn seat_id(input: BoardingPass, result: usize) {
         assert_eq!(input.seat_id(), result);
     }
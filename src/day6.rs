@@ -1,8 +1,9 @@
-use itertools::Itertools;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
 use std::collections::HashSet;
 use std::iter::FromIterator;
 
-struct Group {
+pub struct Group {
     people: Vec<HashSet<char>>,
 }
 
@@ -17,7 +18,7 @@ impl Group {
     fn all_yes_count(&self) -> usize {
         self.people
             .iter()
-            .fold(HashSet::from_iter(('a'..='z')), |a, b| (&a & b).clone())
+            .fold(HashSet::from_iter('a'..='z'), |a, b| (&a & b).clone())
             .len()
     }
 }
@@ -39,27 +40,45 @@ impl Group {
 //     )
 // }
 
-pub fn run() {
-    let input = include_str!("data/6/1");
-    let groups = input
-        .split("\n\n")
-        .map(|group| Group {
-            people: group
-                .lines()
-                .map(|line| HashSet::from_iter(line.chars()))
-                .collect_vec(),
+fn person(group: &str, line: &str) -> Result<HashSet<char>, ParseError> {
+    match line.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+        Some((index, c)) => Err(parse::error_at(
+            group,
+            &line[index..],
+            format!("unexpected answer '{}'", c),
+        )),
+        None => Ok(HashSet::from_iter(line.chars())),
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<Group>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::blocks(input, |group| {
+            Ok(Group {
+                people: parse::list(group, "\n", |line| person(group, line))?,
+            })
         })
-        .collect_vec();
+    }
 
-    println!(
-        "{}",
-        groups.iter().map(|group| group.yes_count()).sum::<usize>()
-    );
-    println!(
-        "{}",
-        groups
+    fn part1(input: &Self::Input) -> Answer {
+        input
             .iter()
-            .map(|group| group.all_yes_count())
+            .map(|group| group.yes_count())
             .sum::<usize>()
-    );
+            .into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(
+            input
+                .iter()
+                .map(|group| group.all_yes_count())
+                .sum::<usize>()
+                .into(),
+        )
+    }
 }
@@ -1,10 +1,26 @@
-use crate::maybe_from::MaybeFrom;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
 use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
 use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt;
 use std::iter::FromIterator;
 
-#[derive(Debug)]
-struct Rules {
+const ADJECTIVES: &[&str] = &[
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "mirrored",
+    "muted", "pale", "plaid", "posh", "shiny", "striped", "vibrant", "wavy",
+];
+
+const COLORS: &[&str] = &[
+    "black", "blue", "coral", "cyan", "gold", "green", "lime", "olive", "orange", "plum", "red",
+    "tan", "teal", "violet", "white", "yellow",
+];
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Rules {
     bag_rules: HashMap<String, HashMap<String, usize>>,
 }
 
@@ -14,7 +30,7 @@ impl Rules {
         for (bag_color, rules) in &self.bag_rules {
             if rules.contains_key(color) {
                 colors.insert(bag_color.clone());
-                colors.extend(self.can_contain(&bag_color));
+                colors.extend(self.can_contain(bag_color));
             }
         }
         colors
@@ -26,38 +42,138 @@ impl Rules {
             .map(|rules| {
                 rules
                     .iter()
-                    .map(|(key, value)| {
-                        value.clone() + (value.clone() * self.count_required_bags(key))
-                    })
+                    .map(|(key, value)| *value + (*value * self.count_required_bags(key)))
                     .sum()
             })
             .unwrap_or(0)
     }
 }
 
-impl From<&str> for Rules {
-    fn from(value: &str) -> Self {
-        Rules {
-            bag_rules: HashMap::from_iter(value.lines().flat_map(|line| {
-                let (color, rules) = line.split_once(" bags contain ")?;
-                if rules == "no other bags." {
-                    Some((color.to_string(), HashMap::new()))
-                } else {
-                    Some((
-                        color.to_string(),
-                        HashMap::from_iter(rules.split(", ").flat_map(|rule| {
-                            let (num, color) = rule.rsplit_once(" bag")?.0.split_once(" ")?;
-                            Some((color.to_string(), num.parse::<usize>().ok()?))
-                        })),
-                    ))
-                }
-            })),
+fn contents(line: &str, rules: &str) -> Result<HashMap<String, usize>, ParseError> {
+    if rules == "no other bags" {
+        return Ok(HashMap::new());
+    }
+    parse::list(rules, ", ", |rule| {
+        let (num, rest) = parse::split(line, rule, " ")?;
+        let (color, _) = parse::split(line, rest, " bag")?;
+        Ok((color.to_string(), parse::number(line, num)?))
+    })
+    .map(HashMap::from_iter)
+}
+
+impl TryFrom<&str> for Rules {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let bag_rules = parse::lines(value, |line| {
+            let (color, rules) = parse::split(line, line, " bags contain ")?;
+            let rules = parse::terminated(line, rules, ".")?;
+            Ok((color.to_string(), contents(line, rules)?))
+        })?;
+        Ok(Rules {
+            bag_rules: HashMap::from_iter(bag_rules),
+        })
+    }
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (color, rules) in self.bag_rules.iter().sorted_by_key(|(color, _)| *color) {
+            if rules.is_empty() {
+                writeln!(f, "{} bags contain no other bags.", color)?;
+                continue;
+            }
+            let contents = rules
+                .iter()
+                .sorted()
+                .map(|(color, count)| match count {
+                    1 => format!("1 {} bag", color),
+                    _ => format!("{} {} bags", count, color),
+                })
+                .join(", ");
+            writeln!(f, "{} bags contain {}.", color, contents)?;
         }
+        Ok(())
+    }
+}
+
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut colors = ADJECTIVES
+        .iter()
+        .cartesian_product(COLORS)
+        .map(|(adjective, color)| format!("{} {}", adjective, color))
+        .filter(|color| color != "shiny gold")
+        .collect_vec();
+    colors.shuffle(rng);
+    colors.truncate(size - 1);
+    colors.push("shiny gold".to_string());
+    let depths = colors
+        .iter()
+        .map(|color| match color.as_str() {
+            "shiny gold" => 2,
+            _ => rng.gen_range(0..5),
+        })
+        .collect_vec();
+    let mut bag_rules = HashMap::new();
+    for (color, depth) in colors.iter().zip(&depths) {
+        let inner = colors
+            .iter()
+            .zip(&depths)
+            .filter(|(_, inner)| **inner == depth + 1)
+            .map(|(color, _)| color)
+            .collect_vec();
+        let count = rng.gen_range(0..=inner.len().min(3));
+        let inner = inner.choose_multiple(rng, count).cloned().collect_vec();
+        let rules = inner
+            .into_iter()
+            .map(|color| (color.clone(), rng.gen_range(1..=5)))
+            .collect();
+        bag_rules.insert(color.clone(), rules);
     }
+    Rules { bag_rules }.to_string()
 }
 
-pub fn run() {
-    let rules = Rules::from(include_str!("data/7/1"));
-    println!("{}", rules.can_contain("shiny gold").len());
-    println!("{}", rules.count_required_bags("shiny gold"));
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Rules;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Rules::try_from(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.can_contain("shiny gold").len().into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(input.count_required_bags("shiny gold").into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+    use rand::SeedableRng;
+
+    #[parameterized(seed = { 0, 1, 2, 3, 4 })]
+    fn round_trip(seed: u64) {
+        let input = generate(&mut StdRng::seed_from_u64(seed), 30);
+        let rules = Rules::try_from(input.as_str()).unwrap();
+        assert_eq!(rules.to_string(), input);
+        assert_eq!(Rules::try_from(rules.to_string().as_str()), Ok(rules));
+    }
+
+    #[parameterized(case = {
+        ("light red bags contain 1 bright white bag, x muted yellow bags.", "line 1, column 44: invalid number 'x'"),
+        ("light red bags contain no other bags", "line 1, column 37: expected '.'"),
+        ("light red bags hold 2 shiny gold bags.", "line 1, column 1: expected ' bags contain '"),
+    })]
+    fn invalid_rules(case: (&str, &str)) {
+        assert_eq!(
+            Rules::try_from(case.0).map_err(|error| error.to_string()),
+            Err(case.1.to_string())
+        );
+    }
 }
@@ -1,6 +1,14 @@
 use crate::maybe_from::MaybeFrom;
+use crate::parse::{self, ParseError};
+use crate::render;
+use crate::solution::{Answer, Solution};
+use crate::trace::{self, Event};
 use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::Rng;
 use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum Opcode {
@@ -9,18 +17,38 @@ enum Opcode {
     Jmp(i32),
 }
 
-impl MaybeFrom<&str> for Opcode {
-    fn maybe_from(value: &str) -> Option<Self> {
-        let (operation, argument) = value.split_once(" ")?;
-        argument
-            .parse::<i32>()
-            .ok()
-            .and_then(|arg| match operation {
-                "nop" => Some(Opcode::Nop(arg)),
-                "acc" => Some(Opcode::Acc(arg)),
-                "jmp" => Some(Opcode::Jmp(arg)),
-                _ => None,
-            })
+#[derive(Debug, Copy, Clone, MaybeFrom)]
+enum Operation {
+    #[token("nop")]
+    Nop,
+    #[token("acc")]
+    Acc,
+    #[token("jmp")]
+    Jmp,
+}
+
+impl TryFrom<&str> for Opcode {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (operation, argument) = parse::split(value, value, " ")?;
+        let operation = parse::token(value, operation, "operation")?;
+        let arg = parse::number(value, argument)?;
+        Ok(match operation {
+            Operation::Nop => Opcode::Nop(arg),
+            Operation::Acc => Opcode::Acc(arg),
+            Operation::Jmp => Opcode::Jmp(arg),
+        })
+    }
+}
+
+impl fmt::Display for Opcode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Opcode::Nop(arg) => write!(f, "nop {:+}", arg),
+            Opcode::Acc(arg) => write!(f, "acc {:+}", arg),
+            Opcode::Jmp(arg) => write!(f, "jmp {:+}", arg),
+        }
     }
 }
 
@@ -32,8 +60,8 @@ enum GameboyState {
     Looped,
 }
 
-#[derive(Debug, Eq, PartialEq)]
-struct Gameboy {
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Gameboy {
     rom: Vec<Opcode>,
     ins: usize,
     acc: i32,
@@ -68,9 +96,9 @@ impl Gameboy {
         self.rom
             .get(at)
             .and_then(|operation| match operation {
-                Opcode::Nop(value) => Some(patch_rom(&self.rom, at, Opcode::Jmp(value.clone()))),
+                Opcode::Nop(value) => Some(patch_rom(&self.rom, at, Opcode::Jmp(*value))),
                 Opcode::Acc(_) => None,
-                Opcode::Jmp(by) => Some(patch_rom(&self.rom, at, Opcode::Nop(by.clone()))),
+                Opcode::Jmp(by) => Some(patch_rom(&self.rom, at, Opcode::Nop(*by))),
             })
             .map(|rom| Gameboy {
                 rom,
@@ -81,17 +109,22 @@ impl Gameboy {
     }
 }
 
-impl From<&str> for Gameboy {
-    fn from(value: &str) -> Self {
-        Self {
-            rom: value
-                .lines()
-                .flat_map(|line| Opcode::maybe_from(line))
-                .collect(),
+impl TryFrom<&str> for Gameboy {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(Self {
+            rom: parse::lines(value, |line| Opcode::try_from(line))?,
             ins: 0,
             acc: 0,
             seen: HashSet::new(),
-        }
+        })
+    }
+}
+
+impl fmt::Display for Gameboy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", render::lines(&self.rom))
     }
 }
 
@@ -107,6 +140,13 @@ fn patch_rom(vec: &[Opcode], at: usize, with: Opcode) -> Vec<Opcode> {
 fn boot(gameboy: &mut Gameboy) -> GameboyState {
     let mut seen = HashSet::<usize>::new();
     while seen.insert(gameboy.ins) {
+        if let Some(operation) = gameboy.rom.get(gameboy.ins) {
+            trace::emit(Event::Instruction {
+                address: gameboy.ins,
+                instruction: operation,
+                accumulator: gameboy.acc as i64,
+            });
+        }
         match gameboy.advance() {
             GameboyState::Normal => (),
             state => return state,
@@ -115,28 +155,63 @@ fn boot(gameboy: &mut Gameboy) -> GameboyState {
     GameboyState::Looped
 }
 
-pub fn run() {
-    let mut gameboy = Gameboy::from(include_str!("data/8/1"));
-    boot(&mut gameboy);
-    println!("{}", gameboy.acc);
-    println!(
-        "{}",
-        (0..gameboy.rom.len())
-            .find_map(|at| gameboy
-                .flip(at)
-                .as_mut()
-                .and_then(|patched| match boot(patched) {
-                    GameboyState::Booted => Some(patched.acc),
-                    _ => None,
-                }))
-            .expect("no booted gamebody found")
-    );
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let corrupted = rng.gen_range(1..size);
+    (0..size)
+        .map(|at| {
+            let (operation, argument) = if at == corrupted {
+                ("jmp", -rng.gen_range(1..=corrupted.min(10) as i32))
+            } else {
+                let limit = if at < corrupted { corrupted } else { size };
+                match rng.gen_range(0..3) {
+                    0 => ("nop", rng.gen_range(-99..=99)),
+                    1 => ("acc", rng.gen_range(-99..=99)),
+                    _ => ("jmp", rng.gen_range(1..=(limit - at).min(10) as i32)),
+                }
+            };
+            format!("{} {:+}\n", operation, argument)
+        })
+        .collect()
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Gameboy;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Gameboy::try_from(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let mut gameboy = input.clone();
+        boot(&mut gameboy);
+        gameboy.acc.into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(
+            (0..input.rom.len())
+                .find_map(|at| {
+                    input
+                        .flip(at)
+                        .as_mut()
+                        .and_then(|patched| match boot(patched) {
+                            GameboyState::Booted => Some(patched.acc),
+                            _ => None,
+                        })
+                })
+                .expect("no booted gamebody found")
+                .into(),
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use parameterized::parameterized;
+    use rand::SeedableRng;
 
     #[parameterized(flip = {
         0,
@@ -159,7 +234,6 @@ mod tests {
         }),
         None
     })]
-    #[test]
     fn tests_patch_rom(flip: usize, result: Option<Gameboy>) {
         let gameboy = Gameboy {
             rom: vec![Opcode::Jmp(1), Opcode::Acc(2), Opcode::Nop(3)],
@@ -169,4 +243,24 @@ mod tests {
         };
         assert_eq!(gameboy.flip(flip), result);
     }
+
+    #[parameterized(case = {
+        ("nop +0\nacc 1\njmp x4", "line 3, column 5: invalid number 'x4'"),
+        ("nop +0\nmul +3", "line 2, column 1: unknown operation 'mul'"),
+        ("acc", "line 1, column 1: expected ' '"),
+    })]
+    fn invalid_rom(case: (&str, &str)) {
+        assert_eq!(
+            Gameboy::try_from(case.0).map_err(|error| error.to_string()),
+            Err(case.1.to_string())
+        );
+    }
+
+    #[parameterized(seed = { 0, 1, 2, 3, 4 })]
+    fn round_trip(seed: u64) {
+        let input = generate(&mut StdRng::seed_from_u64(seed), 30);
+        let gameboy = Gameboy::try_from(input.as_str()).unwrap();
+        assert_eq!(gameboy.to_string(), input);
+        assert_eq!(Gameboy::try_from(gameboy.to_string().as_str()), Ok(gameboy));
+    }
 }
@@ -1,4 +1,9 @@
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Implementation, Solution};
 use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::seq::index;
+use rand::Rng;
 use std::collections::VecDeque;
 use std::iter::FromIterator;
 
@@ -16,19 +21,21 @@ fn find_first_error(nums: &[u64], buf_size: usize) -> Option<u64> {
     nums.iter().skip(buf_size).find_map(|num| {
         if valid(num, &buffer) {
             buffer.pop_front();
-            buffer.push_back(num.clone());
+            buffer.push_back(*num);
             None
         } else {
-            Some(num.clone())
+            Some(*num)
         }
     })
 }
 
+pub const PREAMBLE: usize = 25;
+
 fn find_run(nums: &[u64], error: u64) -> Option<Vec<u64>> {
-    (2..nums.len()).find_map(|length| {
-        nums.windows(length).find_map(|candidates| {
-            if candidates.iter().cloned().sum::<u64>() == error {
-                Some(candidates.to_vec())
+    (0..nums.len()).find_map(|start| {
+        (start + 2..=nums.len()).find_map(|end| {
+            if nums[start..end].iter().sum::<u64>() == error {
+                Some(nums[start..end].to_vec())
             } else {
                 None
             }
@@ -36,13 +43,116 @@ fn find_run(nums: &[u64], error: u64) -> Option<Vec<u64>> {
     })
 }
 
-pub fn run() {
-    let nums = include_str!("data/9/1")
-        .lines()
-        .flat_map(|line| line.parse::<u64>().ok())
-        .collect_vec();
-    let error = find_first_error(&nums, 25).expect("no errors found");
-    println!("{}", error);
-    let run = find_run(&nums, error).expect("no run found");
-    println!("{}", run.iter().max().unwrap() + run.iter().min().unwrap());
+fn find_run_sliding(nums: &[u64], error: u64) -> Option<Vec<u64>> {
+    let mut start = 0;
+    let mut sum = 0;
+    for end in 0..nums.len() {
+        sum += nums[end];
+        while sum > error || (sum == error && start == end) {
+            sum -= nums[start];
+            start += 1;
+        }
+        if sum == error && end > start {
+            return Some(nums[start..=end].to_vec());
+        }
+    }
+    None
+}
+
+fn weakness(run: Option<Vec<u64>>) -> Answer {
+    let run = run.expect("no run found");
+    (run.iter().max().unwrap() + run.iter().min().unwrap()).into()
+}
+
+fn runs(nums: &[u64], error: u64) -> usize {
+    (0..nums.len())
+        .filter(|&start| {
+            nums[start..]
+                .iter()
+                .scan(0, |sum, num| {
+                    *sum += num;
+                    Some(*sum)
+                })
+                .skip(1)
+                .take_while(|&sum| sum <= error)
+                .any(|sum| sum == error)
+        })
+        .count()
+}
+
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    loop {
+        let mut nums = index::sample(rng, 50, PREAMBLE)
+            .into_iter()
+            .map(|num| num as u64 + 1)
+            .collect_vec();
+        let next = |nums: &[u64], rng: &mut StdRng| {
+            let smallest = nums[nums.len() - PREAMBLE..]
+                .iter()
+                .unique()
+                .sorted()
+                .take(5)
+                .collect_vec();
+            let (a, b) = loop {
+                let a = rng.gen_range(0..smallest.len());
+                let b = rng.gen_range(0..smallest.len());
+                if a != b {
+                    break (a, b);
+                }
+            };
+            smallest[a] + smallest[b]
+        };
+        let position = rng.gen_range(PREAMBLE..size);
+        while nums.len() < position {
+            let num = next(&nums, rng);
+            nums.push(num);
+        }
+        let start = rng.gen_range(0..position - 1);
+        let end = rng.gen_range(start + 2..=position);
+        let error = nums[start..end].iter().sum::<u64>();
+        let window = VecDeque::from_iter(nums[position - PREAMBLE..].iter().cloned());
+        if valid(&error, &window) {
+            continue;
+        }
+        nums.push(error);
+        while nums.len() < size {
+            let num = next(&nums, rng);
+            nums.push(num);
+        }
+        if runs(&nums, error) == 1 {
+            return nums.iter().map(|num| format!("{}\n", num)).collect();
+        }
+    }
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::lines(input, |line| parse::number(line, line))
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        find_first_error(input, PREAMBLE)
+            .expect("no errors found")
+            .into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        let error = find_first_error(input, PREAMBLE).expect("no errors found");
+        Some(weakness(find_run_sliding(input, error)))
+    }
+
+    fn alternatives() -> Vec<Implementation<Self::Input>> {
+        vec![Implementation {
+            name: "brute-force",
+            part: 2,
+            solve: |input| {
+                let error = find_first_error(input, PREAMBLE).expect("no errors found");
+                weakness(find_run(input, error))
+            },
+        }]
+    }
 }
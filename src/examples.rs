@@ -0,0 +1,217 @@
+use crate::input;
+use crate::parse::ParseError;
+use crate::pool::{self, Outcome as PoolOutcome};
+use crate::registry::Day;
+use crate::solution::Answer;
+use crate::verify::{self, Outcome};
+use itertools::Itertools;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SEPARATOR: &str = "---";
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Example {
+    pub name: String,
+    pub answers: BTreeMap<u8, Answer>,
+    pub input: String,
+}
+
+impl Example {
+    pub fn parse(name: &str, content: &str) -> Result<Self, ParseError> {
+        let mut answers = BTreeMap::new();
+        let mut lines = content.split_inclusive('\n').enumerate();
+        loop {
+            let (index, line) = lines
+                .next()
+                .ok_or_else(|| ParseError::new(format!("missing '{}' after header", SEPARATOR)))?;
+            let line = line.trim();
+            if line == SEPARATOR {
+                break;
+            }
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid =
+                || ParseError::new(format!("invalid header '{}'", line)).on_line(index + 1);
+            let (key, answer) = line.split_once(':').ok_or_else(invalid)?;
+            let part = key
+                .trim()
+                .strip_prefix("part")
+                .and_then(|part| part.parse::<u8>().ok())
+                .ok_or_else(invalid)?;
+            let answer = answer.trim().parse().unwrap_or_else(|never| match never {});
+            if answers.insert(part, answer).is_some() {
+                return Err(
+                    ParseError::new(format!("duplicate answer for part {}", part))
+                        .on_line(index + 1),
+                );
+            }
+        }
+        if answers.is_empty() {
+            return Err(ParseError::new("no expected answers in header"));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            answers,
+            input: lines.map(|(_, line)| line).collect(),
+        })
+    }
+}
+
+pub fn dir(day: u8) -> PathBuf {
+    input::data_dir().join(day.to_string()).join("examples")
+}
+
+pub fn load_dir(path: &Path) -> Result<Vec<Example>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let entries = fs::read_dir(path)
+        .map_err(|error| format!("failed to read {}: {}", path.display(), error))?;
+    entries
+        .map(|entry| {
+            entry
+                .map(|entry| entry.path())
+                .map_err(|error| format!("failed to read {}: {}", path.display(), error))
+        })
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .filter(|path| path.is_file())
+        .sorted()
+        .map(|path| {
+            let name = path
+                .file_stem()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let content = fs::read_to_string(&path)
+                .map_err(|error| format!("failed to read {}: {}", path.display(), error))?;
+            Example::parse(&name, &content)
+                .map_err(|error| format!("{}: {}", path.display(), error))
+        })
+        .collect()
+}
+
+pub fn load(day: u8) -> Result<Vec<Example>, String> {
+    load_dir(&dir(day))
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Check {
+    pub day: u8,
+    pub example: String,
+    pub part: u8,
+    pub outcome: Outcome,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {} example {} part {}: {}",
+            self.day, self.example, self.part, self.outcome
+        )
+    }
+}
+
+fn solve(day: &Day, example: &Example) -> Result<Vec<(u8, Option<Answer>)>, String> {
+    let parsed = day
+        .solver
+        .parse(&example.input)
+        .map_err(|error| error.to_string())?;
+    Ok(example
+        .answers
+        .keys()
+        .map(|&part| (part, parsed.solve(part)))
+        .collect())
+}
+
+pub fn check(day: &Day, example: &Example) -> Vec<Check> {
+    let check = |part, outcome| Check {
+        day: day.number,
+        example: example.name.clone(),
+        part,
+        outcome,
+    };
+    let error = |error: String| {
+        example
+            .answers
+            .keys()
+            .map(|&part| check(part, Outcome::Error(error.clone())))
+            .collect()
+    };
+    match pool::run(&[example], 1, |example| solve(day, example)).pop() {
+        Some(PoolOutcome::Success(answers)) => answers
+            .into_iter()
+            .map(|(part, actual)| {
+                check(
+                    part,
+                    match actual {
+                        Some(actual) => verify::compare(example.answers.get(&part), actual),
+                        None => Outcome::Error(format!("day {} has no part {}", day.number, part)),
+                    },
+                )
+            })
+            .collect(),
+        Some(PoolOutcome::Failure(message)) => error(message),
+        Some(PoolOutcome::Panic(message)) => error(format!("panicked: {}", message)),
+        None => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use parameterized::parameterized;
+
+    #[test]
+    fn parse_example() {
+        let example = Example::parse(
+            "small",
+            "# from the puzzle text\npart1: 514579\npart2: abc\n---\n1721\n979\n",
+        )
+        .unwrap();
+        assert_eq!(example.name, "small");
+        assert_eq!(example.answers.get(&1), Some(&Answer::Int(514579)));
+        assert_eq!(
+            example.answers.get(&2),
+            Some(&Answer::Text("abc".to_string()))
+        );
+        assert_eq!(example.input, "1721\n979\n");
+    }
+
+    #[parameterized(case = {
+        ("part1: 5\n1\n2\n", "line 2: invalid header '1'"),
+        ("part1: 5\n", "missing '---' after header"),
+        ("---\n1\n", "no expected answers in header"),
+        ("part1: 5\npart1: 6\n---\n", "line 2: duplicate answer for part 1"),
+        ("answer: 5\n---\n", "line 1: invalid header 'answer: 5'"),
+    })]
+    fn invalid_example(case: (&str, &str)) {
+        assert_eq!(
+            Example::parse("bad", case.0).map_err(|error| error.to_string()),
+            Err(case.1.to_string())
+        );
+    }
+
+    #[test]
+    fn failing_example() {
+        let example =
+            Example::parse("wrong", "part1: 1\n---\n1721\n979\n366\n299\n675\n1456\n").unwrap();
+        assert_eq!(
+            check(registry::find(1).unwrap(), &example),
+            vec![Check {
+                day: 1,
+                example: "wrong".to_string(),
+                part: 1,
+                outcome: Outcome::Fail {
+                    expected: Answer::Int(1),
+                    actual: Answer::Int(514579),
+                },
+            }]
+        );
+    }
+}
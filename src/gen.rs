@@ -0,0 +1,181 @@
+use crate::{day1, day12, day13, day14, day16, day18, day2, day5, day7, day8, day9};
+use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+pub struct Generator {
+    pub day: u8,
+    pub sizes: RangeInclusive<usize>,
+    pub generate: fn(&mut StdRng, usize) -> String,
+}
+
+pub const GENERATORS: &[Generator] = &[
+    Generator {
+        day: 1,
+        sizes: 5..=1000,
+        generate: day1::generate,
+    },
+    Generator {
+        day: 2,
+        sizes: 1..=usize::MAX,
+        generate: day2::generate,
+    },
+    Generator {
+        day: 5,
+        sizes: 2..=1021,
+        generate: day5::generate,
+    },
+    Generator {
+        day: 7,
+        sizes: 1..=usize::MAX,
+        generate: day7::generate,
+    },
+    Generator {
+        day: 8,
+        sizes: 2..=usize::MAX,
+        generate: day8::generate,
+    },
+    Generator {
+        day: 9,
+        sizes: day9::PREAMBLE + 2..=usize::MAX,
+        generate: day9::generate,
+    },
+    Generator {
+        day: 12,
+        sizes: 1..=usize::MAX,
+        generate: day12::generate,
+    },
+    Generator {
+        day: 13,
+        sizes: 2..=usize::MAX,
+        generate: day13::generate,
+    },
+    Generator {
+        day: 14,
+        sizes: 1..=usize::MAX,
+        generate: day14::generate,
+    },
+    Generator {
+        day: 16,
+        sizes: 1..=usize::MAX,
+        generate: day16::generate,
+    },
+    Generator {
+        day: 18,
+        sizes: 1..=usize::MAX,
+        generate: day18::generate,
+    },
+];
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum GenError {
+    NoGenerator(u8),
+    InvalidSize { day: u8, size: usize },
+}
+
+impl fmt::Display for GenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenError::NoGenerator(day) => write!(
+                f,
+                "day {} has no input generator (available days: {})",
+                day,
+                GENERATORS.iter().map(|generator| generator.day).join(", ")
+            ),
+            GenError::InvalidSize { day, size } => {
+                let sizes = find(*day).map(|generator| generator.sizes.clone());
+                match sizes {
+                    Ok(sizes) if *sizes.end() == usize::MAX => write!(
+                        f,
+                        "day {} needs a size of at least {}, got {}",
+                        day,
+                        sizes.start(),
+                        size
+                    ),
+                    Ok(sizes) => write!(
+                        f,
+                        "day {} needs a size between {} and {}, got {}",
+                        day,
+                        sizes.start(),
+                        sizes.end(),
+                        size
+                    ),
+                    Err(error) => error.fmt(f),
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for GenError {}
+
+pub fn find(day: u8) -> Result<&'static Generator, GenError> {
+    GENERATORS
+        .iter()
+        .find(|generator| generator.day == day)
+        .ok_or(GenError::NoGenerator(day))
+}
+
+pub fn generate(day: u8, size: usize, seed: u64) -> Result<String, GenError> {
+    let generator = find(day)?;
+    if !generator.sizes.contains(&size) {
+        return Err(GenError::InvalidSize { day, size });
+    }
+    Ok((generator.generate)(&mut StdRng::seed_from_u64(seed), size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use parameterized::parameterized;
+
+    #[parameterized(day = { 1, 2, 5, 7, 8, 9, 12, 13, 14, 16, 18 })]
+    fn solvable(day: u8) {
+        let solver = registry::find(day).unwrap();
+        for seed in 0..20 {
+            let input = generate(day, 50, seed).unwrap();
+            let parsed = solver.solver.parse(&input).unwrap();
+            for part in solver.parts() {
+                assert!(parsed.solve(part).is_some());
+            }
+        }
+    }
+
+    #[parameterized(day = { 1, 2, 5, 7, 8, 9, 12, 13, 14, 16, 18 })]
+    fn deterministic(day: u8) {
+        assert_eq!(generate(day, 30, 7), generate(day, 30, 7));
+        assert_ne!(generate(day, 30, 7), generate(day, 30, 8));
+    }
+
+    #[test]
+    fn missing_generator() {
+        assert_eq!(generate(3, 10, 0), Err(GenError::NoGenerator(3)));
+    }
+
+    #[parameterized(case = {
+        (1, 4, "day 1 needs a size between 5 and 1000, got 4"),
+        (1, 5000, "day 1 needs a size between 5 and 1000, got 5000"),
+        (5, 1022, "day 5 needs a size between 2 and 1021, got 1022"),
+        (9, 10, "day 9 needs a size of at least 27, got 10"),
+        (18, 0, "day 18 needs a size of at least 1, got 0"),
+    })]
+    fn invalid_size(case: (u8, usize, &str)) {
+        assert_eq!(
+            generate(case.0, case.1, 0).map_err(|error| error.to_string()),
+            Err(case.2.to_string())
+        );
+    }
+
+    #[parameterized(day = { 1, 2, 5, 7, 8, 9, 12, 13, 14, 16, 18 })]
+    fn size_bounds(day: u8) {
+        let generator = find(day).unwrap();
+        let smallest = *generator.sizes.start();
+        let largest = (*generator.sizes.end()).min(1000);
+        for size in [smallest, largest] {
+            assert!(generate(day, size, 0).is_ok());
+        }
+    }
+}
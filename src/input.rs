@@ -0,0 +1,86 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Source {
+    Default,
+    Stdin,
+    File(PathBuf),
+}
+
+impl From<&str> for Source {
+    fn from(value: &str) -> Self {
+        match value {
+            "-" => Source::Stdin,
+            path => Source::File(PathBuf::from(path)),
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default input"),
+            Source::Stdin => write!(f, "stdin"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    day: u8,
+    location: String,
+    error: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to read input for day {} from {}: {}",
+            self.day, self.location, self.error
+        )
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub fn data_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("data")
+}
+
+pub fn path_in(data_dir: &Path, day: u8) -> PathBuf {
+    data_dir.join(day.to_string()).join("1")
+}
+
+pub fn default_path(day: u8) -> PathBuf {
+    path_in(&data_dir(), day)
+}
+
+pub fn load(day: u8, source: &Source) -> Result<String, InputError> {
+    let path = match source {
+        Source::Default => default_path(day),
+        Source::File(path) => path.clone(),
+        Source::Stdin => {
+            let mut input = String::new();
+            return io::stdin()
+                .read_to_string(&mut input)
+                .map(|_| input)
+                .map_err(|error| InputError {
+                    day,
+                    location: source.to_string(),
+                    error,
+                });
+        }
+    };
+    fs::read_to_string(&path).map_err(|error| InputError {
+        day,
+        location: path.display().to_string(),
+        error,
+    })
+}
@@ -0,0 +1,40 @@
+#![feature(step_trait)]
+pub mod bench;
+pub mod client;
+pub mod crosscheck;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod examples;
+pub mod gen;
+pub mod input;
+pub mod maybe_from;
+pub mod nd;
+pub mod output;
+pub mod parse;
+pub mod pool;
+pub mod registry;
+pub mod render;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+pub mod trace;
+pub mod twod;
+pub mod utils;
+pub mod verify;
+pub mod watch;
@@ -1,28 +1,374 @@
-#![feature(str_split_once, step_trait)]
-#![allow(unused)]
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod maybe_from;
-mod render;
-mod twod;
-mod utils;
+mod cli;
+
+use aoc2020::client::{self, Client};
+use aoc2020::input::{self, Source};
+use aoc2020::output::{self, Format, Record};
+use aoc2020::parse::{self, Mode};
+use aoc2020::pool::{self, Outcome};
+use aoc2020::registry::{self, Day, LookupError, DAYS};
+use aoc2020::scaffold;
+use aoc2020::submit::{self, History, Verdict};
+use aoc2020::verify::{self, Answers};
+use aoc2020::watch::{Change, Watcher};
+use aoc2020::{bench, crosscheck, gen, trace};
+use cli::{Command, Selection};
+use itertools::Itertools;
+use std::error::Error;
+use std::path::PathBuf;
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+fn list() {
+    for day in DAYS {
+        println!(
+            "day {:>2}: {}",
+            day.number,
+            day.parts().map(|part| format!("part {}", part)).join(", ")
+        );
+    }
+}
+
+fn run_day(day: &Day, part: Option<u8>, source: &Source) -> Result<Vec<Record>, Box<dyn Error>> {
+    run_input(day, part, &input::load(day.number, source)?)
+}
+
+fn run_input(day: &Day, part: Option<u8>, input: &str) -> Result<Vec<Record>, Box<dyn Error>> {
+    let parts = match part {
+        Some(part) if day.has_part(part) => part..=part,
+        Some(part) => {
+            return Err(LookupError::UnknownPart {
+                day: day.number,
+                part,
+            }
+            .into())
+        }
+        None => day.parts(),
+    };
+    trace::with_day(day.number, || {
+        let start = Instant::now();
+        let parsed = day.solver.parse(input)?;
+        let parse_time = start.elapsed();
+        for error in parse::take_skipped() {
+            eprintln!("warning: day {}: skipped {}", day.number, error);
+        }
+        Ok(parts
+            .filter_map(|part| {
+                let start = Instant::now();
+                let answer = parsed.solve(part)?;
+                Some(Record {
+                    day: day.number,
+                    part,
+                    answer,
+                    parse_time,
+                    solve_time: start.elapsed(),
+                })
+            })
+            .collect())
+    })
+}
+
+fn print_records(records: &[Record], format: Format) {
+    match format {
+        Format::Text => {
+            for record in records {
+                println!("{}", output::text(record));
+            }
+        }
+        Format::Json => println!("{}", output::json(records)),
+    }
+}
+
+fn run_all(
+    part: Option<u8>,
+    source: &Source,
+    format: Format,
+    jobs: usize,
+) -> Result<(), Box<dyn Error>> {
+    let days = DAYS
+        .iter()
+        .filter(|day| part.is_none_or(|p| day.has_part(p)))
+        .collect_vec();
+    let outcomes = pool::run(&days, jobs, |day| {
+        run_day(day, part, source).map_err(|error| error.to_string())
+    });
+    let mut records = Vec::new();
+    let mut failures = Vec::new();
+    for (day, outcome) in days.iter().zip(outcomes) {
+        match outcome {
+            Outcome::Success(day_records) => records.extend(day_records),
+            Outcome::Failure(error) => failures.push(format!("day {}: {}", day.number, error)),
+            Outcome::Panic(message) => {
+                failures.push(format!("day {}: panicked: {}", day.number, message))
+            }
+        }
+    }
+    print_records(&records, format);
+    let summary = format!(
+        "{} days: {} succeeded, {} failed",
+        days.len(),
+        days.len() - failures.len(),
+        failures.len()
+    );
+    match format {
+        Format::Text => println!("{}", summary),
+        Format::Json => eprintln!("{}", summary),
+    }
+    for failure in &failures {
+        eprintln!("{}", failure);
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("{} of {} days failed", failures.len(), days.len()).into())
+    }
+}
+
+fn run(
+    selection: Selection,
+    part: Option<u8>,
+    source: &Source,
+    format: Format,
+    jobs: usize,
+    mode: Mode,
+    trace: &trace::Options,
+) -> Result<(), Box<dyn Error>> {
+    trace.install()?;
+    parse::set_mode(mode);
+    match selection {
+        Selection::Day(number) => {
+            print_records(&run_day(registry::find(number)?, part, source)?, format);
+            Ok(())
+        }
+        Selection::All => run_all(part, source, format, jobs),
+    }
+}
+
+fn verify(selection: Selection, answers: Option<PathBuf>) -> Result<bool, Box<dyn Error>> {
+    let answers = Answers::load(&answers.unwrap_or_else(Answers::default_path))?;
+    let days = match selection {
+        Selection::Day(number) => vec![registry::find(number)?],
+        Selection::All => DAYS.iter().collect(),
+    };
+    let checks = days
+        .into_iter()
+        .flat_map(|day| verify::check_day(day, &answers))
+        .collect_vec();
+    println!("{}", verify::report(&checks));
+    Ok(checks.iter().all(|check| check.outcome.is_pass()))
+}
+
+fn bench(
+    selection: Selection,
+    source: &Source,
+    options: &bench::Options,
+    save: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+) -> Result<(), Box<dyn Error>> {
+    let baseline = baseline
+        .map(|path| bench::Baseline::load(&path))
+        .transpose()?;
+    let days = match selection {
+        Selection::Day(number) => vec![registry::find(number)?],
+        Selection::All => DAYS.iter().collect(),
+    };
+    let mut measurements = Vec::new();
+    for day in days {
+        let input = input::load(day.number, source)?;
+        measurements.extend(bench::bench_day(day, &input, options)?);
+    }
+    println!("{}", bench::report(&measurements, baseline.as_ref()));
+    if let Some(path) = save {
+        bench::Baseline::save(&path, &measurements)?;
+    }
+    Ok(())
+}
+
+fn watch(
+    day: u8,
+    part: Option<u8>,
+    path: Option<PathBuf>,
+    interval: Duration,
+    trace: &trace::Options,
+) -> Result<(), Box<dyn Error>> {
+    trace.install()?;
+    let day = registry::find(day)?;
+    if let Some(part) = part.filter(|&part| !day.has_part(part)) {
+        return Err(LookupError::UnknownPart {
+            day: day.number,
+            part,
+        }
+        .into());
+    }
+    let path = path.unwrap_or_else(|| input::default_path(day.number));
+    println!("watching {}", path.display());
+    let mut watcher = Watcher::new(path.clone());
+    loop {
+        match watcher.poll() {
+            Change::Unchanged => (),
+            Change::Failed(error) => eprintln!("error: {}: {}", path.display(), error),
+            Change::Modified(input) => {
+                let outcome = pool::run(&[day], 1, |day| {
+                    run_input(day, part, &input).map_err(|error| error.to_string())
+                });
+                match outcome.into_iter().next() {
+                    Some(Outcome::Success(records)) => {
+                        for record in records {
+                            println!("{}", output::timed(&record));
+                        }
+                    }
+                    Some(Outcome::Failure(error)) => eprintln!("error: {}", error),
+                    Some(Outcome::Panic(message)) => eprintln!("error: panicked: {}", message),
+                    None => (),
+                }
+            }
+        }
+        thread::sleep(interval);
+    }
+}
+
+fn gen(day: u8, size: usize, seed: u64) -> Result<(), Box<dyn Error>> {
+    print!("{}", gen::generate(day, size, seed)?);
+    Ok(())
+}
+
+fn crosscheck(
+    selection: Selection,
+    cases: usize,
+    size: usize,
+    seed: u64,
+) -> Result<bool, Box<dyn Error>> {
+    let days = match selection {
+        Selection::Day(number) => vec![registry::find(number)?],
+        Selection::All => DAYS
+            .iter()
+            .filter(|day| gen::find(day.number).is_ok() && !crosscheck::parts(day).is_empty())
+            .collect(),
+    };
+    let mut mismatches = Vec::new();
+    for day in days {
+        if crosscheck::parts(day).is_empty() {
+            println!("day {}: only one implementation per part", day.number);
+            continue;
+        }
+        let found = crosscheck::run(day, cases, size, seed)?;
+        if found.is_empty() {
+            println!("day {}: {} cases agree", day.number, cases);
+        }
+        mismatches.extend(found);
+    }
+    for mismatch in &mismatches {
+        println!("mismatch: {}", mismatch);
+    }
+    Ok(mismatches.is_empty())
+}
+
+fn new(day: u8) -> Result<(), Box<dyn Error>> {
+    for path in scaffold::create(&scaffold::source_dir(), day)? {
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn fetch(day: u8, force: bool) -> Result<(), Box<dyn Error>> {
+    registry::find(day)?;
+    let fetched = Client::new(client::Config::load()?).fetch(day, force)?;
+    if fetched.cached {
+        println!("day {}: already cached at {}", day, fetched.path.display());
+    } else {
+        println!("day {}: saved to {}", day, fetched.path.display());
+    }
+    Ok(())
+}
+
+fn submit(
+    day: u8,
+    part: u8,
+    source: &Source,
+    history: Option<PathBuf>,
+) -> Result<(), Box<dyn Error>> {
+    let answer = run_day(registry::find(day)?, Some(part), source)?
+        .pop()
+        .ok_or_else(|| format!("day {} part {} did not produce an answer", day, part))?
+        .answer;
+    let path = history.unwrap_or_else(History::default_path);
+    let mut history = History::load(&path)?;
+    let client = Client::new(client::Config::load()?);
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let verdict = submit::submit(&client, &mut history, day, part, answer.clone(), now);
+    history.save(&path)?;
+    match verdict? {
+        Verdict::Correct => println!("day {} part {}: {} is correct", day, part, answer),
+        Verdict::TooHigh => println!("day {} part {}: {} is too high", day, part, answer),
+        Verdict::TooLow => println!("day {} part {}: {} is too low", day, part, answer),
+        Verdict::Incorrect => println!("day {} part {}: {} is not correct", day, part, answer),
+        Verdict::Wait(seconds) => println!("submitted too recently, wait {} seconds", seconds),
+        Verdict::AlreadySolved => println!("day {} part {} is already solved", day, part),
+        Verdict::Unknown => println!("unrecognised response for day {} part {}", day, part),
+    }
+    Ok(())
+}
 
 fn main() {
-    day18::run();
+    let command = cli::parse(std::env::args().skip(1)).unwrap_or_else(|error| {
+        eprintln!("error: {}\n\n{}", error, cli::USAGE);
+        process::exit(2);
+    });
+    let result = match command {
+        Command::List => {
+            list();
+            Ok(())
+        }
+        Command::Run {
+            selection,
+            part,
+            input,
+            format,
+            jobs,
+            mode,
+            trace,
+        } => run(selection, part, &input, format, jobs, mode, &trace),
+        Command::Verify { selection, answers } => match verify(selection, answers) {
+            Ok(true) => Ok(()),
+            Ok(false) => process::exit(1),
+            Err(error) => Err(error),
+        },
+        Command::Bench {
+            selection,
+            input,
+            options,
+            save,
+            baseline,
+        } => bench(selection, &input, &options, save, baseline),
+        Command::Watch {
+            day,
+            part,
+            input,
+            interval,
+            trace,
+        } => watch(day, part, input, interval, &trace),
+        Command::New { day } => new(day),
+        Command::Fetch { day, force } => fetch(day, force),
+        Command::Submit {
+            day,
+            part,
+            input,
+            history,
+        } => submit(day, part, &input, history),
+        Command::Gen { day, size, seed } => gen(day, size, seed),
+        Command::Crosscheck {
+            selection,
+            cases,
+            size,
+            seed,
+        } => match crosscheck(selection, cases, size, seed) {
+            Ok(true) => Ok(()),
+            Ok(false) => process::exit(1),
+            Err(error) => Err(error),
+        },
+    };
+    if let Err(error) = result {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
 }
@@ -1,3 +1,4 @@
+pub use aoc2020_derive::MaybeFrom;
 use std::convert::TryFrom;
 
 pub trait MaybeFrom<T>: Sized {
@@ -22,3 +23,50 @@ impl<T: MaybeFrom<U>, U: Sized> MaybeInto<T> for U {
         T::maybe_from(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::Render;
+    use parameterized::parameterized;
+
+    #[derive(Debug, Eq, PartialEq, MaybeFrom)]
+    enum Cell {
+        #[token('.')]
+        Empty,
+        #[token('#')]
+        Wall,
+    }
+
+    #[derive(Debug, Eq, PartialEq, MaybeFrom)]
+    enum Word {
+        #[token(parse)]
+        Number(i32),
+        #[token("up")]
+        Up,
+    }
+
+    #[parameterized(case = {
+        ('.', Some(Cell::Empty)),
+        ('#', Some(Cell::Wall)),
+        ('x', None),
+    })]
+    fn derived_char(case: (char, Option<Cell>)) {
+        assert_eq!(Cell::maybe_from(case.0), case.1);
+        if let Some(cell) = case.1 {
+            assert_eq!(cell.render(), case.0.to_string());
+        }
+    }
+
+    #[parameterized(case = {
+        ("up", Some(Word::Up)),
+        ("-12", Some(Word::Number(-12))),
+        ("down", None),
+    })]
+    fn derived_str(case: (&str, Option<Word>)) {
+        assert_eq!(Word::maybe_from(case.0), case.1);
+        if let Some(word) = case.1 {
+            assert_eq!(word.to_string(), case.0);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use std::ops::{Add, AddAssign, Index, Mul, Neg, Sub};
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct PointN<const D: usize> {
+    coords: [i32; D],
+}
+
+impl<const D: usize> PointN<D> {
+    pub fn new(coords: [i32; D]) -> Self {
+        Self { coords }
+    }
+
+    pub fn origin() -> Self {
+        Self::new([0; D])
+    }
+
+    pub fn embed(coords: &[i32]) -> Self {
+        let mut point = Self::origin();
+        for (axis, coord) in coords.iter().take(D).enumerate() {
+            point.coords[axis] = *coord;
+        }
+        point
+    }
+
+    pub fn coords(&self) -> &[i32; D] {
+        &self.coords
+    }
+
+    pub fn neighbors(&self) -> impl Iterator<Item = Self> + '_ {
+        let count = 3usize.pow(D as u32);
+        (0..count)
+            .filter(move |&offset| offset != count / 2)
+            .map(move |offset| {
+                let mut point = *self;
+                let mut digits = offset;
+                for coord in point.coords.iter_mut() {
+                    *coord += (digits % 3) as i32 - 1;
+                    digits /= 3;
+                }
+                point
+            })
+    }
+}
+
+impl<const D: usize> Default for PointN<D> {
+    fn default() -> Self {
+        Self::origin()
+    }
+}
+
+impl<const D: usize> Index<usize> for PointN<D> {
+    type Output = i32;
+
+    fn index(&self, axis: usize) -> &Self::Output {
+        &self.coords[axis]
+    }
+}
+
+impl<const D: usize> Add for PointN<D> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self::Output {
+        self += other;
+        self
+    }
+}
+
+impl<const D: usize> AddAssign for PointN<D> {
+    fn add_assign(&mut self, other: Self) {
+        for (coord, other) in self.coords.iter_mut().zip(other.coords.iter()) {
+            *coord += other;
+        }
+    }
+}
+
+impl<const D: usize> Sub for PointN<D> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + -other
+    }
+}
+
+impl<const D: usize> Neg for PointN<D> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self * -1
+    }
+}
+
+impl<const D: usize> Mul<i32> for PointN<D> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: i32) -> Self::Output {
+        for coord in self.coords.iter_mut() {
+            *coord *= rhs;
+        }
+        self
+    }
+}
+
+impl<const D: usize> From<[i32; D]> for PointN<D> {
+    fn from(coords: [i32; D]) -> Self {
+        Self::new(coords)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Extent<const D: usize> {
+    pub min: PointN<D>,
+    pub max: PointN<D>,
+}
+
+impl<const D: usize> Extent<D> {
+    pub fn of<'a, I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = &'a PointN<D>>,
+    {
+        let mut points = points.into_iter();
+        let first = *points.next()?;
+        Some(points.fold(
+            Self {
+                min: first,
+                max: first,
+            },
+            |mut extent, point| {
+                for axis in 0..D {
+                    extent.min.coords[axis] = extent.min.coords[axis].min(point[axis]);
+                    extent.max.coords[axis] = extent.max.coords[axis].max(point[axis]);
+                }
+                extent
+            },
+        ))
+    }
+
+    pub fn grow(&self, by: i32) -> Self {
+        Self {
+            min: self.min - PointN::new([by; D]),
+            max: self.max + PointN::new([by; D]),
+        }
+    }
+
+    pub fn contains(&self, point: &PointN<D>) -> bool {
+        (0..D).all(|axis| (self.min[axis]..=self.max[axis]).contains(&point[axis]))
+    }
+
+    pub fn len(&self, axis: usize) -> usize {
+        (self.max[axis] - self.min[axis] + 1).max(0) as usize
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = PointN<D>> + '_ {
+        let count = (0..D).map(|axis| self.len(axis)).product::<usize>();
+        (0..count).map(move |index| {
+            let mut point = self.min;
+            let mut rest = index;
+            for axis in 0..D {
+                point.coords[axis] += (rest % self.len(axis)) as i32;
+                rest /= self.len(axis);
+            }
+            point
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+    use parameterized::parameterized;
+    use std::collections::HashSet;
+
+    #[parameterized(dimensions = { 1, 2, 3, 4 })]
+    fn neighbor_count(dimensions: usize) {
+        let count = match dimensions {
+            1 => PointN::<1>::origin().neighbors().unique().count(),
+            2 => PointN::<2>::origin().neighbors().unique().count(),
+            3 => PointN::<3>::origin().neighbors().unique().count(),
+            _ => PointN::<4>::origin().neighbors().unique().count(),
+        };
+        assert_eq!(count, 3usize.pow(dimensions as u32) - 1);
+    }
+
+    #[test]
+    fn neighbors() {
+        let point = PointN::new([5, -2]);
+        assert_eq!(
+            point.neighbors().collect::<HashSet<_>>(),
+            [
+                [4, -3],
+                [5, -3],
+                [6, -3],
+                [4, -2],
+                [6, -2],
+                [4, -1],
+                [5, -1],
+                [6, -1]
+            ]
+            .iter()
+            .map(|coords| PointN::new(*coords))
+            .collect()
+        );
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = PointN::new([1, 2, 3]);
+        let b = PointN::new([-1, 0, 4]);
+        assert_eq!(a + b, PointN::new([0, 2, 7]));
+        assert_eq!(a - b, PointN::new([2, 2, -1]));
+        assert_eq!(a * 2, PointN::new([2, 4, 6]));
+        assert_eq!(-a, PointN::new([-1, -2, -3]));
+        assert_eq!(PointN::<4>::embed(&[1, 2]), PointN::new([1, 2, 0, 0]));
+    }
+
+    #[test]
+    fn extent() {
+        let points = vec![
+            PointN::new([1, 5]),
+            PointN::new([-2, 3]),
+            PointN::new([0, 4]),
+        ];
+        let extent = Extent::of(&points).unwrap();
+        assert_eq!(extent.min, PointN::new([-2, 3]));
+        assert_eq!(extent.max, PointN::new([1, 5]));
+        assert_eq!((extent.len(0), extent.len(1)), (4, 3));
+        assert_eq!(extent.points().count(), 12);
+        assert!(extent.points().all(|point| extent.contains(&point)));
+        assert!(!extent.contains(&PointN::new([2, 4])));
+        assert_eq!(extent.grow(1).points().count(), 30);
+        assert_eq!(Extent::<2>::of(&[]), None);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::solution::Answer;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+impl From<&Answer> for Value {
+    fn from(answer: &Answer) -> Self {
+        match answer {
+            Answer::Int(value) => json!(value),
+            Answer::Text(value) => json!(value),
+        }
+    }
+}
+
+pub fn text(record: &Record) -> String {
+    format!("day {} part {}: {}", record.day, record.part, record.answer)
+}
+
+pub fn timed(record: &Record) -> String {
+    format!(
+        "{} (parse {:.1?}, solve {:.1?})",
+        text(record),
+        record.parse_time,
+        record.solve_time
+    )
+}
+
+pub fn json(records: &[Record]) -> String {
+    Value::Array(
+        records
+            .iter()
+            .map(|record| {
+                json!({
+                    "day": record.day,
+                    "part": record.part,
+                    "answer": Value::from(&record.answer),
+                    "parse_time_ns": record.parse_time.as_nanos() as u64,
+                    "solve_time_ns": record.solve_time.as_nanos() as u64,
+                })
+            })
+            .collect(),
+    )
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_records() {
+        let records = [
+            Record {
+                day: 1,
+                part: 2,
+                answer: Answer::Int(42),
+                parse_time: Duration::from_nanos(1500),
+                solve_time: Duration::from_micros(3),
+            },
+            Record {
+                day: 7,
+                part: 1,
+                answer: Answer::Text("a\"b".to_string()),
+                parse_time: Duration::default(),
+                solve_time: Duration::default(),
+            },
+        ];
+        assert_eq!(
+            json(&records),
+            r#"[{"answer":42,"day":1,"parse_time_ns":1500,"part":2,"solve_time_ns":3000},{"answer":"a\"b","day":7,"parse_time_ns":0,"part":1,"solve_time_ns":0}]"#
+        );
+    }
+
+    #[test]
+    fn timed_record() {
+        let record = Record {
+            day: 3,
+            part: 1,
+            answer: Answer::Int(7),
+            parse_time: Duration::from_micros(1250),
+            solve_time: Duration::from_nanos(800),
+        };
+        assert_eq!(
+            timed(&record),
+            "day 3 part 1: 7 (parse 1.2ms, solve 800.0ns)"
+        );
+    }
+}
@@ -0,0 +1,381 @@
+use crate::maybe_from::MaybeFrom;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    reason: String,
+    line: Option<usize>,
+    column: Option<usize>,
+}
+
+impl ParseError {
+    pub fn new<S: Into<String>>(reason: S) -> Self {
+        Self {
+            reason: reason.into(),
+            line: None,
+            column: None,
+        }
+    }
+
+    pub fn at<S: Into<String>>(column: usize, reason: S) -> Self {
+        Self {
+            column: Some(column),
+            ..Self::new(reason)
+        }
+    }
+
+    pub fn on_line(self, line: usize) -> Self {
+        Self {
+            line: Some(line),
+            ..self
+        }
+    }
+
+    pub fn shifted(self, lines: usize) -> Self {
+        let line = self.line.unwrap_or(1) + lines;
+        self.on_line(line)
+    }
+
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => {
+                write!(f, "line {}, column {}: {}", line, column, self.reason)
+            }
+            (Some(line), None) => write!(f, "line {}: {}", line, self.reason),
+            (None, Some(column)) => write!(f, "column {}: {}", column, self.reason),
+            (None, None) => write!(f, "{}", self.reason),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Mode {
+    Strict,
+    Lenient,
+}
+
+static LENIENT: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static SKIPPED: RefCell<Vec<ParseError>> = const { RefCell::new(Vec::new()) };
+}
+
+pub fn set_mode(mode: Mode) {
+    LENIENT.store(mode == Mode::Lenient, Ordering::Relaxed);
+}
+
+pub fn mode() -> Mode {
+    if LENIENT.load(Ordering::Relaxed) {
+        Mode::Lenient
+    } else {
+        Mode::Strict
+    }
+}
+
+pub fn take_skipped() -> Vec<ParseError> {
+    SKIPPED.with(|skipped| skipped.take())
+}
+
+pub fn column(line: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+    line.get(..offset.min(line.len()))
+        .map_or(1, |prefix| prefix.chars().count() + 1)
+}
+
+pub fn error_at<S: Into<String>>(source: &str, part: &str, reason: S) -> ParseError {
+    let offset = (part.as_ptr() as usize).saturating_sub(source.as_ptr() as usize);
+    let prefix = source.get(..offset.min(source.len())).unwrap_or_default();
+    let line = prefix.matches('\n').count();
+    let current = prefix.rsplit('\n').next().unwrap_or_default();
+    let error = ParseError::at(current.chars().count() + 1, reason);
+    match source.trim_end_matches('\n').contains('\n') {
+        true => error.on_line(line + 1),
+        false => error,
+    }
+}
+
+pub fn number<T: FromStr>(source: &str, value: &str) -> Result<T, ParseError> {
+    value
+        .parse()
+        .map_err(|_| error_at(source, value, format!("invalid number '{}'", value)))
+}
+
+pub fn token<'a, T: MaybeFrom<&'a str>>(
+    source: &str,
+    value: &'a str,
+    what: &str,
+) -> Result<T, ParseError> {
+    T::maybe_from(value)
+        .ok_or_else(|| error_at(source, value, format!("unknown {} '{}'", what, value)))
+}
+
+pub fn literal<'a>(source: &str, value: &'a str, expected: &str) -> Result<&'a str, ParseError> {
+    value
+        .strip_prefix(expected)
+        .ok_or_else(|| error_at(source, value, format!("expected '{}'", expected)))
+}
+
+pub fn terminated<'a>(source: &str, value: &'a str, expected: &str) -> Result<&'a str, ParseError> {
+    value.strip_suffix(expected).ok_or_else(|| {
+        error_at(
+            source,
+            &value[value.len()..],
+            format!("expected '{}'", expected),
+        )
+    })
+}
+
+pub fn split<'a>(
+    source: &str,
+    value: &'a str,
+    separator: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    value
+        .split_once(separator)
+        .ok_or_else(|| error_at(source, value, format!("expected '{}'", separator)))
+}
+
+pub fn list<'a, T, F>(value: &'a str, separator: &str, item: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&'a str) -> Result<T, ParseError>,
+{
+    value.split(separator).map(item).collect()
+}
+
+pub fn pairs<'a>(
+    source: &str,
+    value: &'a str,
+    separator: &str,
+) -> Result<HashMap<&'a str, &'a str>, ParseError> {
+    let mut pairs = HashMap::new();
+    for pair in value.split_whitespace() {
+        let (key, value) = split(source, pair, separator)?;
+        if pairs.insert(key, value).is_some() {
+            return Err(error_at(source, pair, format!("duplicate key '{}'", key)));
+        }
+    }
+    Ok(pairs)
+}
+
+pub fn blocks<'a, T, F>(input: &'a str, mut parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&'a str) -> Result<T, ParseError>,
+{
+    let mut blocks = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for (index, line) in input.split_inclusive('\n').enumerate() {
+        match (line.trim().is_empty(), start) {
+            (true, Some((first, begin))) => {
+                blocks.push((first, &input[begin..offset]));
+                start = None;
+            }
+            (false, None) => start = Some((index, offset)),
+            _ => (),
+        }
+        offset += line.len();
+    }
+    if let Some((first, begin)) = start {
+        blocks.push((first, &input[begin..]));
+    }
+    blocks
+        .into_iter()
+        .map(|(first, block)| parse(block.trim_end()).map_err(|error| error.shifted(first)))
+        .collect()
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Lines<T> {
+    pub values: Vec<T>,
+    pub skipped: Vec<ParseError>,
+}
+
+pub fn lines_with<T, F>(input: &str, mode: Mode, mut parse: F) -> Result<Lines<T>, ParseError>
+where
+    F: FnMut(&str) -> Result<T, ParseError>,
+{
+    let mut lines = Lines {
+        values: Vec::new(),
+        skipped: Vec::new(),
+    };
+    for (index, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match parse(line) {
+            Ok(value) => lines.values.push(value),
+            Err(error) => match mode {
+                Mode::Strict => return Err(error.on_line(index + 1)),
+                Mode::Lenient => lines.skipped.push(error.on_line(index + 1)),
+            },
+        }
+    }
+    Ok(lines)
+}
+
+pub fn lines<T, F>(input: &str, parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&str) -> Result<T, ParseError>,
+{
+    let Lines { values, skipped } = lines_with(input, mode(), parse)?;
+    SKIPPED.with(|cell| cell.borrow_mut().extend(skipped));
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+
+    fn even(line: &str) -> Result<u32, ParseError> {
+        number(line, line).and_then(|value: u32| match value % 2 {
+            0 => Ok(value),
+            _ => Err(ParseError::at(1, "odd number")),
+        })
+    }
+
+    #[parameterized(case = {
+        (ParseError::new("bad"), "bad"),
+        (ParseError::at(4, "bad"), "column 4: bad"),
+        (ParseError::new("bad").on_line(2), "line 2: bad"),
+        (ParseError::at(4, "bad").on_line(2), "line 2, column 4: bad"),
+    })]
+    fn display(case: (ParseError, &str)) {
+        assert_eq!(case.0.to_string(), case.1);
+    }
+
+    #[parameterized(case = {
+        ("abc", 0, 1),
+        ("abc", 2, 3),
+        ("äbc", 2, 2),
+        ("abc", 3, 4),
+    })]
+    fn columns(case: (&str, usize, usize)) {
+        assert_eq!(column(case.0, &case.0[case.1..]), case.2);
+    }
+
+    #[parameterized(case = {
+        ("abc", 1, "column 2: bad"),
+        ("ab\ncd\nef", 4, "line 2, column 2: bad"),
+        ("ab\ncd\nef", 6, "line 3, column 1: bad"),
+        ("ab\ncd\nef", 1, "line 1, column 2: bad"),
+        ("abc\n", 1, "column 2: bad"),
+    })]
+    fn located_errors(case: (&str, usize, &str)) {
+        assert_eq!(
+            error_at(case.0, &case.0[case.1..], "bad").to_string(),
+            case.2
+        );
+    }
+
+    #[derive(Debug, Eq, PartialEq, MaybeFrom)]
+    enum Letter {
+        #[token("a")]
+        A,
+    }
+
+    #[test]
+    fn primitives() {
+        let line = "1-3 a: abc";
+        let (rule, password) = split(line, line, ": ").unwrap();
+        assert_eq!((rule, password), ("1-3 a", "abc"));
+        assert_eq!(literal(line, password, "ab"), Ok("c"));
+        assert_eq!(terminated(line, password, "bc"), Ok("a"));
+        assert_eq!(
+            list("1,2,3", ",", |value| number::<u8>("1,2,3", value)),
+            Ok(vec![1, 2, 3])
+        );
+        let numbers = "1,x";
+        assert_eq!(
+            list(numbers, ",", |value| number::<u8>(numbers, value)),
+            Err(ParseError::at(3, "invalid number 'x'"))
+        );
+        assert_eq!(token(line, &rule[4..], "letter"), Ok(Letter::A));
+        assert_eq!(
+            token::<Letter>(line, rule, "letter").map_err(|error| error.to_string()),
+            Err("column 1: unknown letter '1-3 a'".to_string())
+        );
+    }
+
+    type Primitive = fn(&str) -> Result<(), ParseError>;
+
+    #[parameterized(case = {
+        ("a b", |source| split(source, source, ", ").map(|_| ()), "column 1: expected ', '"),
+        ("a b", |source| literal(source, &source[2..], "x").map(|_| ()), "column 3: expected 'x'"),
+        ("a b", |source| terminated(source, source, ".").map(|_| ()), "column 4: expected '.'"),
+        ("a:1 b2", |source| pairs(source, source, ":").map(|_| ()), "column 5: expected ':'"),
+        ("a:1 a:2", |source| pairs(source, source, ":").map(|_| ()), "column 5: duplicate key 'a'"),
+    })]
+    fn primitive_errors(case: (&str, Primitive, &str)) {
+        assert_eq!(
+            case.1(case.0).map_err(|error| error.to_string()),
+            Err(case.2.to_string())
+        );
+    }
+
+    #[test]
+    fn key_value_pairs() {
+        let pairs = pairs("a:1\nb:2 c:3", "a:1\nb:2 c:3", ":").unwrap();
+        assert_eq!(pairs.len(), 3);
+        assert_eq!(pairs.get("b"), Some(&"2"));
+    }
+
+    #[test]
+    fn blank_line_blocks() {
+        let input = "1\n2\n\n\n3\n4\nx\n";
+        assert_eq!(
+            blocks("1\n2\n\n3\n", |block| Ok(block.to_string())),
+            Ok(vec!["1\n2".to_string(), "3".to_string()])
+        );
+        assert_eq!(
+            blocks(input, |block| block
+                .lines()
+                .map(|line| number::<u8>(block, line))
+                .collect::<Result<Vec<_>, _>>())
+            .map_err(|error| error.to_string()),
+            Err("line 7, column 1: invalid number 'x'".to_string())
+        );
+    }
+
+    #[test]
+    fn strict() {
+        assert_eq!(
+            lines_with("2\n4\n\n5\nx\n", Mode::Strict, even),
+            Err(ParseError::at(1, "odd number").on_line(4))
+        );
+    }
+
+    #[test]
+    fn lenient() {
+        assert_eq!(
+            lines_with("2\n4\n\n5\nx\n", Mode::Lenient, even),
+            Ok(Lines {
+                values: vec![2, 4],
+                skipped: vec![
+                    ParseError::at(1, "odd number").on_line(4),
+                    ParseError::at(1, "invalid number 'x'").on_line(5),
+                ],
+            })
+        );
+    }
+}
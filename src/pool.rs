@@ -0,0 +1,87 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Outcome<T> {
+    Success(T),
+    Failure(String),
+    Panic(String),
+}
+
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+pub fn run<I, T, F>(items: &[I], jobs: usize, f: F) -> Vec<Outcome<T>>
+where
+    I: Sync,
+    T: Send,
+    F: Fn(&I) -> Result<T, String> + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let sender = sender.clone();
+            let (next, f) = (&next, &f);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let item = match items.get(index) {
+                    Some(item) => item,
+                    None => break,
+                };
+                let outcome = match panic::catch_unwind(AssertUnwindSafe(|| f(item))) {
+                    Ok(Ok(value)) => Outcome::Success(value),
+                    Ok(Err(error)) => Outcome::Failure(error),
+                    Err(payload) => Outcome::Panic(panic_message(payload)),
+                };
+                sender
+                    .send((index, outcome))
+                    .expect("result receiver dropped");
+            });
+        }
+    });
+    drop(sender);
+    let mut outcomes = receiver.into_iter().collect::<Vec<_>>();
+    outcomes.sort_by_key(|(index, _)| *index);
+    outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+
+    #[parameterized(jobs = { 1, 2, 8 })]
+    fn isolates_failures(jobs: usize) {
+        let items = (0..10).collect::<Vec<u32>>();
+        let outcomes = run(&items, jobs, |&item| match item {
+            3 => Err("three".to_string()),
+            7 => panic!("seven"),
+            item => Ok(item * 2),
+        });
+        assert_eq!(outcomes.len(), 10);
+        assert_eq!(outcomes[0], Outcome::Success(0));
+        assert_eq!(outcomes[3], Outcome::Failure("three".to_string()));
+        assert_eq!(outcomes[7], Outcome::Panic("seven".to_string()));
+        assert_eq!(outcomes[9], Outcome::Success(18));
+    }
+
+    #[test]
+    fn formatted_panic_message() {
+        let outcomes = run(&[5], 1, |item: &u32| -> Result<u32, String> {
+            panic!("unexpected item {}", item)
+        });
+        assert_eq!(
+            outcomes,
+            vec![Outcome::Panic("unexpected item 5".to_string())]
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use crate::solution::Solver;
+use crate::{
+    day1, day10, day11, day12, day13, day14, day15, day16, day17, day18, day2, day3, day4, day5,
+    day6, day7, day8, day9,
+};
+use itertools::Itertools;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+pub struct Day {
+    pub number: u8,
+    pub solver: &'static dyn Solver,
+}
+
+impl Day {
+    pub fn parts(&self) -> RangeInclusive<u8> {
+        1..=self.solver.parts()
+    }
+
+    pub fn has_part(&self, part: u8) -> bool {
+        self.parts().contains(&part)
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        solver: &day1::Day1,
+    },
+    Day {
+        number: 2,
+        solver: &day2::Day2,
+    },
+    Day {
+        number: 3,
+        solver: &day3::Day3,
+    },
+    Day {
+        number: 4,
+        solver: &day4::Day4,
+    },
+    Day {
+        number: 5,
+        solver: &day5::Day5,
+    },
+    Day {
+        number: 6,
+        solver: &day6::Day6,
+    },
+    Day {
+        number: 7,
+        solver: &day7::Day7,
+    },
+    Day {
+        number: 8,
+        solver: &day8::Day8,
+    },
+    Day {
+        number: 9,
+        solver: &day9::Day9,
+    },
+    Day {
+        number: 10,
+        solver: &day10::Day10,
+    },
+    Day {
+        number: 11,
+        solver: &day11::Day11,
+    },
+    Day {
+        number: 12,
+        solver: &day12::Day12,
+    },
+    Day {
+        number: 13,
+        solver: &day13::Day13,
+    },
+    Day {
+        number: 14,
+        solver: &day14::Day14,
+    },
+    Day {
+        number: 15,
+        solver: &day15::Day15,
+    },
+    Day {
+        number: 16,
+        solver: &day16::Day16,
+    },
+    Day {
+        number: 17,
+        solver: &day17::Day17,
+    },
+    Day {
+        number: 18,
+        solver: &day18::Day18,
+    },
+];
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum LookupError {
+    UnknownDay(u8),
+    UnknownPart { day: u8, part: u8 },
+}
+
+impl fmt::Display for LookupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LookupError::UnknownDay(day) => write!(
+                f,
+                "day {} is not implemented (available days: {})",
+                day,
+                DAYS.iter().map(|day| day.number).join(", ")
+            ),
+            LookupError::UnknownPart { day, part } => {
+                write!(f, "day {} has no part {}", day, part)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LookupError {}
+
+pub fn find(number: u8) -> Result<&'static Day, LookupError> {
+    DAYS.iter()
+        .find(|day| day.number == number)
+        .ok_or(LookupError::UnknownDay(number))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+
+    #[parameterized(case = {
+        (1, 1, true),
+        (1, 2, true),
+        (1, 3, false),
+        (1, 0, false),
+        (10, 2, false),
+        (18, 1, true),
+    })]
+    fn has_part(case: (u8, u8, bool)) {
+        assert_eq!(find(case.0).unwrap().has_part(case.1), case.2);
+    }
+
+    #[parameterized(day = { 0, 19, 25 })]
+    fn unknown_day(day: u8) {
+        assert_eq!(find(day).err(), Some(LookupError::UnknownDay(day)));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::twod::Point;
+use crate::twod::{Grid, Point};
 use itertools::Itertools;
 use itertools::__std_iter::Step;
 use std::collections::HashMap;
@@ -21,6 +21,18 @@ impl Render for &str {
     }
 }
 
+impl<T: Render> Render for Grid<T> {
+    fn render(&self) -> String {
+        self.rows()
+            .map(|row| row.iter().map(|cell| cell.render()).join(""))
+            .join("\n")
+    }
+}
+
+pub fn lines<T: Display>(items: &[T]) -> String {
+    items.iter().map(|item| format!("{}\n", item)).collect()
+}
+
 pub fn render<T: Hash + Eq + PartialOrd + Clone + Copy + Step, U: Render, B: Render>(
     map: &HashMap<Point<T>, U>,
     background: B,
@@ -0,0 +1,315 @@
+use crate::input;
+use itertools::Itertools;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = r#"use crate::maybe_from::MaybeFrom;
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Entry {
+    value: i64,
+}
+
+impl MaybeFrom<&str> for Entry {
+    fn maybe_from(value: &str) -> Option<Self> {
+        value.parse().ok().map(|value| Self { value })
+    }
+}
+
+pub struct Day{N};
+
+impl Solution for Day{N} {
+    type Input = Vec<Entry>;
+
+    const PARTS: u8 = 1;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::lines(input, |line| {
+            Entry::maybe_from(line)
+                .ok_or_else(|| ParseError::at(1, format!("invalid entry '{}'", line)))
+        })
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        input.iter().map(|entry| entry.value).sum::<i64>().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+
+    #[parameterized(case = {
+        ("1", Some(Entry { value: 1 })),
+        ("x", None),
+    })]
+    fn parse_entry(case: (&str, Option<Entry>)) {
+        assert_eq!(Entry::maybe_from(case.0), case.1);
+    }
+}
+"#;
+
+const MAX_WIDTH: usize = 100;
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    InvalidDay(u8),
+    Exists(PathBuf),
+    Registered { path: PathBuf, day: u8 },
+    Malformed { path: PathBuf, reason: &'static str },
+    Io { path: PathBuf, error: io::Error },
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ScaffoldError::*;
+        match self {
+            InvalidDay(day) => write!(f, "day {} is not between 1 and 25", day),
+            Exists(path) => write!(f, "{} already exists", path.display()),
+            Registered { path, day } => {
+                write!(f, "day {} is already registered in {}", day, path.display())
+            }
+            Malformed { path, reason } => write!(f, "{}: {}", path.display(), reason),
+            Io { path, error } => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+pub fn source_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src")
+}
+
+pub fn module(day: u8) -> String {
+    TEMPLATE.replace("{N}", &day.to_string())
+}
+
+fn register_module(lib: &str, day: u8) -> Option<String> {
+    let name = format!("day{}", day);
+    let line = format!("pub mod {};", name);
+    let module = |line: &str| {
+        line.strip_prefix("pub mod ")
+            .and_then(|line| line.strip_suffix(';'))
+            .map(String::from)
+    };
+    if lib.lines().any(|existing| existing == line) {
+        return None;
+    }
+    let mut lines = lib.lines().collect_vec();
+    let position = lines
+        .iter()
+        .position(|existing| module(existing).is_some_and(|existing| existing > name))
+        .or_else(|| {
+            lines
+                .iter()
+                .rposition(|existing| existing.starts_with("pub mod "))
+                .map(|last| last + 1)
+        })
+        .unwrap_or(lines.len());
+    lines.insert(position, &line);
+    Some(lines.join("\n") + "\n")
+}
+
+fn wrap_imports(names: &[String]) -> String {
+    let single = format!("use crate::{{{}}};", names.join(", "));
+    if single.len() <= MAX_WIDTH {
+        return single + "\n";
+    }
+    let mut wrapped = String::from("use crate::{\n");
+    let mut line = String::from("   ");
+    for name in names {
+        if line.len() + name.len() + 2 > MAX_WIDTH {
+            wrapped += line.trim_end();
+            wrapped += "\n";
+            line = String::from("   ");
+        }
+        line += &format!(" {},", name);
+    }
+    wrapped + &line + "\n};\n"
+}
+
+fn register_day(registry: &str, day: u8) -> Result<String, &'static str> {
+    let (before, rest) = registry
+        .split_once("use crate::{")
+        .ok_or("missing day imports")?;
+    let (imports, after) = rest.split_once("};\n").ok_or("unterminated day imports")?;
+    let mut names = imports
+        .split(',')
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect_vec();
+    names.push(format!("day{}", day));
+    names.sort();
+    let entry = format!(
+        "    Day {{\n        number: {day},\n        solver: &day{day}::Day{day},\n    }},\n",
+        day = day
+    );
+    let (days, tail) = after
+        .split_once("pub const DAYS: &[Day] = &[\n")
+        .ok_or("missing DAYS")?;
+    let (entries, tail) = tail.split_once("];\n").ok_or("unterminated DAYS")?;
+    let mut entries = entries
+        .split_inclusive("    },\n")
+        .map(String::from)
+        .collect_vec();
+    let position = entries
+        .iter()
+        .position(|existing| {
+            existing
+                .split_once("number: ")
+                .and_then(|(_, number)| number.split_once(',')?.0.parse::<u8>().ok())
+                .is_some_and(|number| number > day)
+        })
+        .unwrap_or(entries.len());
+    entries.insert(position, entry);
+    Ok(format!(
+        "{}{}{}pub const DAYS: &[Day] = &[\n{}];\n{}",
+        before,
+        wrap_imports(&names),
+        days,
+        entries.concat(),
+        tail
+    ))
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|error| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+fn write(path: &Path, content: &str) -> Result<(), ScaffoldError> {
+    fs::write(path, content).map_err(|error| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+pub fn create(source_dir: &Path, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    if !(1..=25).contains(&day) {
+        return Err(ScaffoldError::InvalidDay(day));
+    }
+    let module_path = source_dir.join(format!("day{}.rs", day));
+    if module_path.exists() {
+        return Err(ScaffoldError::Exists(module_path));
+    }
+    let lib_path = source_dir.join("lib.rs");
+    let lib = register_module(&read(&lib_path)?, day).ok_or(ScaffoldError::Registered {
+        path: lib_path.clone(),
+        day,
+    })?;
+    let registry_path = source_dir.join("registry.rs");
+    let registry =
+        register_day(&read(&registry_path)?, day).map_err(|reason| ScaffoldError::Malformed {
+            path: registry_path.clone(),
+            reason,
+        })?;
+    let data_path = input::path_in(&source_dir.join("data"), day);
+    let data_dir = data_path.parent().expect("input path has a parent");
+    fs::create_dir_all(data_dir).map_err(|error| ScaffoldError::Io {
+        path: data_dir.to_path_buf(),
+        error,
+    })?;
+    write(&module_path, &module(day))?;
+    write(&lib_path, &lib)?;
+    write(&registry_path, &registry)?;
+    Ok(vec![
+        module_path,
+        lib_path,
+        registry_path,
+        data_dir.to_path_buf(),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+
+    const REGISTRY: &str = "use crate::solution::Solver;
+use crate::{day1, day3};
+use std::fmt;
+
+pub const DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        solver: &day1::Day1,
+    },
+    Day {
+        number: 3,
+        solver: &day3::Day3,
+    },
+];
+
+fn find() {}
+";
+
+    #[parameterized(case = {
+        (2, "pub mod day1;\npub mod day2;\npub mod day3;\npub mod input;\n"),
+        (10, "pub mod day1;\npub mod day10;\npub mod day3;\npub mod input;\n"),
+        (20, "pub mod day1;\npub mod day20;\npub mod day3;\npub mod input;\n"),
+    })]
+    fn module_registration(case: (u8, &str)) {
+        assert_eq!(
+            register_module("pub mod day1;\npub mod day3;\npub mod input;\n", case.0),
+            Some(case.1.to_string())
+        );
+    }
+
+    #[test]
+    fn duplicate_module() {
+        assert_eq!(register_module("pub mod day1;\n", 1), None);
+    }
+
+    #[test]
+    fn day_registration() {
+        let registry = register_day(REGISTRY, 2).unwrap();
+        assert!(registry.contains("use crate::{day1, day2, day3};\n"));
+        assert!(registry.contains(
+            "        solver: &day1::Day1,\n    },\n    Day {\n        number: 2,\n        solver: &day2::Day2,\n    },\n    Day {\n        number: 3,"
+        ));
+        assert!(registry.ends_with("];\n\nfn find() {}\n"));
+    }
+
+    #[test]
+    fn matches_registry_imports() {
+        let registry = include_str!("registry.rs");
+        let (_, imports) = registry.split_once("use crate::{").unwrap();
+        let names = imports
+            .split_once("};")
+            .unwrap()
+            .0
+            .split(',')
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect_vec();
+        assert!(registry.contains(&wrap_imports(&names)));
+    }
+
+    #[test]
+    fn wrapped_imports() {
+        let names = (1..=25)
+            .map(|day| format!("day{}", day))
+            .sorted()
+            .collect_vec();
+        let imports = wrap_imports(&names);
+        assert!(imports.starts_with("use crate::{\n    day1, day10,"));
+        assert!(imports.lines().all(|line| line.len() <= MAX_WIDTH));
+        assert!(imports.ends_with(" day9,\n};\n"));
+    }
+
+    #[test]
+    fn refuses_existing_day() {
+        assert!(matches!(
+            create(&source_dir(), 4),
+            Err(ScaffoldError::Exists(_))
+        ));
+    }
+}
@@ -0,0 +1,202 @@
+use crate::parse::ParseError;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+macro_rules! int_answer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Int(value as i64)
+                }
+            }
+        )*
+    };
+}
+
+int_answer!(i32, i64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(value
+            .parse::<i64>()
+            .map(Answer::Int)
+            .unwrap_or_else(|_| Answer::Text(value.to_string())))
+    }
+}
+
+pub const DEFAULT_IMPLEMENTATION: &str = "default";
+
+pub struct Implementation<I> {
+    pub name: &'static str,
+    pub part: u8,
+    pub solve: fn(&I) -> Answer,
+}
+
+pub trait Solution {
+    type Input;
+
+    const PARTS: u8 = 2;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Answer;
+
+    fn part2(_input: &Self::Input) -> Option<Answer> {
+        None
+    }
+
+    fn alternatives() -> Vec<Implementation<Self::Input>> {
+        Vec::new()
+    }
+}
+
+pub trait Parsed {
+    fn solve(&self, part: u8) -> Option<Answer>;
+
+    fn solve_with(&self, part: u8, implementation: &str) -> Option<Answer>;
+}
+
+pub trait Solver: Sync {
+    fn parts(&self) -> u8;
+
+    fn implementations(&self, part: u8) -> Vec<&'static str>;
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, ParseError>;
+}
+
+struct ParsedInput<S: Solution>(S::Input);
+
+impl<S: Solution> Parsed for ParsedInput<S> {
+    fn solve(&self, part: u8) -> Option<Answer> {
+        match part {
+            1 if S::PARTS >= 1 => Some(S::part1(&self.0)),
+            2 if S::PARTS >= 2 => S::part2(&self.0),
+            _ => None,
+        }
+    }
+
+    fn solve_with(&self, part: u8, implementation: &str) -> Option<Answer> {
+        if implementation == DEFAULT_IMPLEMENTATION {
+            return self.solve(part);
+        }
+        S::alternatives()
+            .into_iter()
+            .find(|alternative| alternative.part == part && alternative.name == implementation)
+            .map(|alternative| (alternative.solve)(&self.0))
+    }
+}
+
+impl<S> Solver for S
+where
+    S: Solution + Sync + 'static,
+{
+    fn parts(&self) -> u8 {
+        S::PARTS
+    }
+
+    fn implementations(&self, part: u8) -> Vec<&'static str> {
+        if !(1..=S::PARTS).contains(&part) {
+            return Vec::new();
+        }
+        std::iter::once(DEFAULT_IMPLEMENTATION)
+            .chain(
+                S::alternatives()
+                    .into_iter()
+                    .filter(|alternative| alternative.part == part)
+                    .map(|alternative| alternative.name),
+            )
+            .collect()
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, ParseError> {
+        S::parse(input).map(|input| Box::new(ParsedInput::<S>(input)) as Box<dyn Parsed>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<i32>;
+
+        const PARTS: u8 = 1;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input
+                .split(',')
+                .map(|num| num.parse().map_err(|_| ParseError::new(num)))
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> Answer {
+            input.iter().sum::<i32>().into()
+        }
+
+        fn alternatives() -> Vec<Implementation<Self::Input>> {
+            vec![Implementation {
+                name: "reversed",
+                part: 1,
+                solve: |input| input.iter().rev().sum::<i32>().into(),
+            }]
+        }
+    }
+
+    #[test]
+    fn answer_from_str() {
+        assert_eq!("-42".parse(), Ok(Answer::Int(-42)));
+        assert_eq!("ABC".parse(), Ok(Answer::Text("ABC".to_string())));
+    }
+
+    #[test]
+    fn solver() {
+        let parsed = Sum.parse("1,2,3").expect("failed to parse");
+        assert_eq!(Sum.parts(), 1);
+        assert_eq!(parsed.solve(1), Some(Answer::Int(6)));
+        assert_eq!(parsed.solve(2), None);
+        assert_eq!(Sum.parse("1,x").err(), Some(ParseError::new("x")));
+    }
+
+    #[test]
+    fn implementations() {
+        let parsed = Sum.parse("1,2,3").expect("failed to parse");
+        assert_eq!(Sum.implementations(1), vec!["default", "reversed"]);
+        assert_eq!(Sum.implementations(2), Vec::<&str>::new());
+        assert_eq!(parsed.solve_with(1, "reversed"), Some(Answer::Int(6)));
+        assert_eq!(parsed.solve_with(1, "default"), Some(Answer::Int(6)));
+        assert_eq!(parsed.solve_with(1, "missing"), None);
+    }
+}
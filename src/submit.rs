@@ -0,0 +1,384 @@
+use crate::client::{Client, ClientError};
+use crate::input;
+use crate::parse::ParseError;
+use crate::solution::Answer;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const PENALTY_SECONDS: u64 = 60;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    Wait(u64),
+    AlreadySolved,
+    Unknown,
+}
+
+impl Verdict {
+    pub fn parse_response(body: &str) -> Self {
+        if body.contains("That's the right answer") {
+            Verdict::Correct
+        } else if body.contains("You gave an answer too recently") {
+            Verdict::Wait(parse_wait(body).unwrap_or(PENALTY_SECONDS))
+        } else if body.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if body.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if body.contains("That's not the right answer") {
+            Verdict::Incorrect
+        } else if body.contains("You don't seem to be solving the right level") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    pub fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+        )
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "correct" => Some(Verdict::Correct),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            "incorrect" => Some(Verdict::Incorrect),
+            "already-solved" => Some(Verdict::AlreadySolved),
+            "unknown" => Some(Verdict::Unknown),
+            wait => wait.strip_prefix("wait:")?.parse().ok().map(Verdict::Wait),
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too-high"),
+            Verdict::TooLow => write!(f, "too-low"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::Wait(seconds) => write!(f, "wait:{}", seconds),
+            Verdict::AlreadySolved => write!(f, "already-solved"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+fn parse_wait(body: &str) -> Option<u64> {
+    let (_, rest) = body.split_once("You have ")?;
+    let (left, _) = rest.split_once(" left to wait")?;
+    left.split_whitespace().try_fold(0, |total, unit| {
+        let (number, scale) = match unit.chars().last()? {
+            'h' => (&unit[..unit.len() - 1], 3600),
+            'm' => (&unit[..unit.len() - 1], 60),
+            's' => (&unit[..unit.len() - 1], 1),
+            _ => return None,
+        };
+        number
+            .parse::<u64>()
+            .ok()
+            .map(|number| total + number * scale)
+    })
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Attempt {
+    pub time: u64,
+    pub day: u8,
+    pub part: u8,
+    pub verdict: Verdict,
+    pub answer: Answer,
+}
+
+impl fmt::Display for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {}",
+            self.time, self.day, self.part, self.verdict, self.answer
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct History {
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    pub fn default_path() -> PathBuf {
+        input::data_dir().join("submissions")
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|error| format!("failed to read {}: {}", path.display(), error))?;
+        Self::parse(&content).map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    pub fn parse(content: &str) -> Result<Self, ParseError> {
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                Self::parse_attempt(line).ok_or_else(|| {
+                    ParseError::new(format!("invalid submission '{}'", line)).on_line(index + 1)
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(|attempts| Self { attempts })
+    }
+
+    fn parse_attempt(line: &str) -> Option<Attempt> {
+        let mut fields = line.splitn(5, ' ');
+        Some(Attempt {
+            time: fields.next()?.parse().ok()?,
+            day: fields.next()?.parse().ok()?,
+            part: fields.next()?.parse().ok()?,
+            verdict: Verdict::parse(fields.next()?)?,
+            answer: fields
+                .next()?
+                .parse()
+                .unwrap_or_else(|never| match never {}),
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_string())
+            .map_err(|error| format!("failed to write {}: {}", path.display(), error))
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt);
+    }
+
+    pub fn not_before(&self) -> u64 {
+        self.attempts
+            .iter()
+            .map(|attempt| match attempt.verdict {
+                Verdict::Wait(seconds) => attempt.time + seconds,
+                ref verdict if verdict.is_wrong() => attempt.time + PENALTY_SECONDS,
+                _ => attempt.time,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &Answer, now: u64) -> Result<(), Refusal> {
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.day == day && attempt.part == part);
+        for attempt in attempts {
+            match (&attempt.verdict, &attempt.answer, answer) {
+                (Verdict::Correct, correct, _) => {
+                    return Err(Refusal::Solved(correct.clone()));
+                }
+                (verdict, tried, answer) if verdict.is_wrong() && tried == answer => {
+                    return Err(Refusal::KnownWrong(verdict.clone()));
+                }
+                (Verdict::TooHigh, Answer::Int(high), Answer::Int(value)) if value >= high => {
+                    return Err(Refusal::KnownWrong(Verdict::TooHigh));
+                }
+                (Verdict::TooLow, Answer::Int(low), Answer::Int(value)) if value <= low => {
+                    return Err(Refusal::KnownWrong(Verdict::TooLow));
+                }
+                _ => (),
+            }
+        }
+        match self.not_before() {
+            not_before if now < not_before => Err(Refusal::RateLimited(not_before - now)),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for History {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for attempt in &self.attempts {
+            writeln!(f, "{}", attempt)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Refusal {
+    Solved(Answer),
+    KnownWrong(Verdict),
+    RateLimited(u64),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::Solved(answer) => write!(f, "already solved with {}", answer),
+            Refusal::KnownWrong(verdict) => {
+                write!(f, "answer is already known to be wrong ({})", verdict)
+            }
+            Refusal::RateLimited(seconds) => {
+                write!(f, "rate limited, wait {} more seconds", seconds)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Refused(Refusal),
+    Client(ClientError),
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Refused(refusal) => write!(f, "refusing to submit: {}", refusal),
+            SubmitError::Client(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+pub fn submit(
+    client: &Client,
+    history: &mut History,
+    day: u8,
+    part: u8,
+    answer: Answer,
+    now: u64,
+) -> Result<Verdict, SubmitError> {
+    history
+        .check(day, part, &answer, now)
+        .map_err(SubmitError::Refused)?;
+    let body = client
+        .submit(day, part, &answer)
+        .map_err(SubmitError::Client)?;
+    let verdict = Verdict::parse_response(&body);
+    history.record(Attempt {
+        time: now,
+        day,
+        part,
+        verdict: verdict.clone(),
+        answer,
+    });
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{mock, Config};
+    use parameterized::parameterized;
+
+    fn attempt(time: u64, part: u8, verdict: Verdict, answer: i64) -> Attempt {
+        Attempt {
+            time,
+            day: 1,
+            part,
+            verdict,
+            answer: Answer::Int(answer),
+        }
+    }
+
+    #[parameterized(case = {
+        ("<p>That's the right answer!  You are one gold star closer.</p>", Verdict::Correct),
+        ("<p>That's not the right answer; your answer is too high.  Please wait one minute</p>", Verdict::TooHigh),
+        ("<p>That's not the right answer; your answer is too low.</p>", Verdict::TooLow),
+        ("<p>That's not the right answer.  If you're stuck</p>", Verdict::Incorrect),
+        ("<p>You gave an answer too recently. You have 36s left to wait.</p>", Verdict::Wait(36)),
+        ("<p>You gave an answer too recently. You have 4m 2s left to wait.</p>", Verdict::Wait(242)),
+        ("<p>You don't seem to be solving the right level.  Did you already complete it?</p>", Verdict::AlreadySolved),
+        ("<html></html>", Verdict::Unknown),
+    })]
+    fn parse_response(case: (&str, Verdict)) {
+        assert_eq!(Verdict::parse_response(case.0), case.1);
+    }
+
+    #[test]
+    fn history_round_trip() {
+        let mut history = History::default();
+        history.record(attempt(100, 1, Verdict::TooLow, 5));
+        history.record(Attempt {
+            answer: Answer::Text("abc def".to_string()),
+            ..attempt(200, 2, Verdict::Wait(30), 0)
+        });
+        assert_eq!(History::parse(&history.to_string()), Ok(history));
+    }
+
+    #[parameterized(case = {
+        (1, 5, 1000, Err(Refusal::KnownWrong(Verdict::TooLow))),
+        (1, 3, 1000, Err(Refusal::KnownWrong(Verdict::TooLow))),
+        (1, 50, 1000, Err(Refusal::KnownWrong(Verdict::TooHigh))),
+        (1, 20, 1000, Ok(())),
+        (1, 20, 230, Err(Refusal::RateLimited(30))),
+        (2, 7, 1000, Err(Refusal::Solved(Answer::Int(8)))),
+    })]
+    fn check(case: (u8, i64, u64, Result<(), Refusal>)) {
+        let history = History {
+            attempts: vec![
+                attempt(100, 1, Verdict::TooLow, 5),
+                attempt(150, 2, Verdict::Correct, 8),
+                attempt(200, 1, Verdict::TooHigh, 40),
+            ],
+        };
+        assert_eq!(
+            history.check(1, case.0, &Answer::Int(case.1), case.2),
+            case.3
+        );
+    }
+
+    #[test]
+    fn submit_and_record() {
+        let server = mock::serve(
+            200,
+            "<p>That's not the right answer; your answer is too low.</p>",
+        );
+        let client = Client::with_data_dir(
+            Config {
+                session: Some("secret".to_string()),
+                base_url: server.url.clone(),
+            },
+            std::env::temp_dir(),
+        );
+        let mut history = History::default();
+        assert_eq!(
+            submit(&client, &mut history, 3, 1, Answer::Int(12), 1000).unwrap(),
+            Verdict::TooLow
+        );
+        assert!(matches!(
+            submit(&client, &mut history, 3, 1, Answer::Int(12), 2000),
+            Err(SubmitError::Refused(Refusal::KnownWrong(Verdict::TooLow)))
+        ));
+        let requests = server.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("POST /2020/day/3/answer "));
+        assert!(requests[0].ends_with("level=1&answer=12"));
+        assert_eq!(
+            history.attempts(),
+            &[Attempt {
+                time: 1000,
+                day: 3,
+                part: 1,
+                verdict: Verdict::TooLow,
+                answer: Answer::Int(12),
+            }]
+        );
+    }
+}
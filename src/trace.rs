@@ -0,0 +1,203 @@
+use std::cell::Cell;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Mutex;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Level {
+    Off,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "off" => Some(Level::Off),
+            "debug" => Some(Level::Debug),
+            "trace" => Some(Level::Trace),
+            _ => None,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Level::Off,
+            1 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Off => write!(f, "off"),
+            Level::Debug => write!(f, "debug"),
+            Level::Trace => write!(f, "trace"),
+        }
+    }
+}
+
+pub enum Event<'a> {
+    Step {
+        step: usize,
+        state: &'a dyn fmt::Display,
+    },
+    Instruction {
+        address: usize,
+        instruction: &'a dyn fmt::Debug,
+        accumulator: i64,
+    },
+    Rejected {
+        record: &'a dyn fmt::Debug,
+        reason: &'a dyn fmt::Display,
+    },
+}
+
+impl Event<'_> {
+    pub fn level(&self) -> Level {
+        match self {
+            Event::Step { .. } | Event::Rejected { .. } => Level::Debug,
+            Event::Instruction { .. } => Level::Trace,
+        }
+    }
+}
+
+impl fmt::Display for Event<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Step { step, state } => write!(f, "step {}\n{}", step, state),
+            Event::Instruction {
+                address,
+                instruction,
+                accumulator,
+            } => write!(f, "{:>4}: {:?} acc={}", address, instruction, accumulator),
+            Event::Rejected { record, reason } => write!(f, "rejected ({}): {:?}", reason, record),
+        }
+    }
+}
+
+pub trait Sink: Send {
+    fn emit(&mut self, day: Option<u8>, event: &Event);
+}
+
+pub struct WriterSink<W: Write + Send>(W);
+
+impl<W: Write + Send> WriterSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self(writer)
+    }
+}
+
+impl<W: Write + Send> Sink for WriterSink<W> {
+    fn emit(&mut self, day: Option<u8>, event: &Event) {
+        let prefix = match day {
+            Some(day) => format!("[{}] day {}: ", event.level(), day),
+            None => format!("[{}] ", event.level()),
+        };
+        writeln!(self.0, "{}{}", prefix, event)
+            .and_then(|_| self.0.flush())
+            .ok();
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+static SINK: Mutex<Option<Box<dyn Sink>>> = Mutex::new(None);
+
+thread_local! {
+    static DAY: Cell<Option<u8>> = const { Cell::new(None) };
+}
+
+pub fn install(level: Level, sink: Box<dyn Sink>) {
+    *SINK.lock().expect("trace sink poisoned") = Some(sink);
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    Level::from_u8(LEVEL.load(Ordering::Relaxed))
+}
+
+fn allows(threshold: Level, level: Level) -> bool {
+    level != Level::Off && level <= threshold
+}
+
+pub fn enabled(level: Level) -> bool {
+    allows(self::level(), level)
+}
+
+pub fn emit(event: Event) {
+    if enabled(event.level()) {
+        if let Some(sink) = SINK.lock().expect("trace sink poisoned").as_mut() {
+            sink.emit(DAY.with(Cell::get), &event);
+        }
+    }
+}
+
+pub fn with_day<T, F: FnOnce() -> T>(day: u8, f: F) -> T {
+    let previous = DAY.with(|current| current.replace(Some(day)));
+    let result = f();
+    DAY.with(|current| current.set(previous));
+    result
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Options {
+    pub level: Level,
+    pub file: Option<PathBuf>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            level: Level::Off,
+            file: None,
+        }
+    }
+}
+
+impl Options {
+    pub fn install(&self) -> io::Result<()> {
+        match &self.file {
+            Some(path) => install(
+                self.level.max(Level::Debug),
+                Box::new(WriterSink::new(File::create(path)?)),
+            ),
+            None => install(self.level, Box::new(WriterSink::new(io::stderr()))),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parameterized::parameterized;
+
+    #[parameterized(case = {
+        (Event::Step { step: 2, state: &"#.\n.#" }, "[debug] day 17: step 2\n#.\n.#\n"),
+        (Event::Instruction { address: 12, instruction: &Some(-3), accumulator: 5 }, "[trace] day 17:   12: Some(-3) acc=5\n"),
+        (Event::Rejected { record: &(1, 2), reason: &"2 invalid fields" }, "[debug] day 17: rejected (2 invalid fields): (1, 2)\n"),
+    })]
+    fn writer_sink(case: (Event, &str)) {
+        let mut sink = WriterSink::new(Vec::new());
+        sink.emit(Some(17), &case.0);
+        assert_eq!(String::from_utf8(sink.0).unwrap(), case.1);
+    }
+
+    #[parameterized(case = {
+        (Level::Off, Level::Off, false),
+        (Level::Off, Level::Debug, false),
+        (Level::Debug, Level::Debug, true),
+        (Level::Debug, Level::Trace, false),
+        (Level::Trace, Level::Trace, true),
+        (Level::Trace, Level::Debug, true),
+        (Level::Trace, Level::Off, false),
+    })]
+    fn levels(case: (Level, Level, bool)) {
+        assert_eq!(allows(case.0, case.1), case.2);
+    }
+}
@@ -1,9 +1,10 @@
 use std::collections::HashMap;
-use std::fmt::{Debug, Display};
+use std::fmt::Debug;
 use std::hash::Hash;
-use std::iter::{FlatMap, Step};
-use std::ops::{Add, AddAssign, Mul, Neg, Rem, Sub};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub};
 
+use crate::maybe_from::MaybeFrom;
+use crate::parse::ParseError;
 use itertools::Itertools;
 use num::{abs, cast, CheckedAdd, CheckedSub, Integer, NumCast, One, Signed, Zero};
 
@@ -26,6 +27,11 @@ where
     pub fn manhattan_distance(&self, other: &Point<T>) -> T {
         distance(self.x, other.x) + distance(self.y, other.y)
     }
+
+    pub fn hex_distance(&self, other: &Point<T>) -> T {
+        let (dq, dr) = (self.x - other.x, self.y - other.y);
+        abs(dq).max(abs(dr)).max(abs(dq + dr))
+    }
 }
 
 impl<T> Point<T>
@@ -33,7 +39,12 @@ where
     T: CheckedSub<Output = T> + CheckedAdd<Output = T> + One + Copy,
 {
     pub fn neighbors(&self) -> impl Iterator<Item = Point<T>> + '_ {
-        NEIGHBORS
+        self.neighbors_in(Neighborhood::Moore)
+    }
+
+    pub fn neighbors_in(&self, neighborhood: Neighborhood) -> impl Iterator<Item = Point<T>> + '_ {
+        neighborhood
+            .directions()
             .iter()
             .flat_map(move |neighbor| neighbor.point::<T>(self, One::one()))
     }
@@ -75,7 +86,7 @@ impl<T: Clone> From<&(T, T)> for Point<T> {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Neighbor {
     TopLeft,
     Top,
@@ -151,6 +162,302 @@ pub const NEIGHBORS: [Neighbor; 8] = [
     Neighbor::BottomRight,
 ];
 
+pub const ORTHOGONAL: [Neighbor; 4] = [
+    Neighbor::Top,
+    Neighbor::Left,
+    Neighbor::Right,
+    Neighbor::Bottom,
+];
+
+// axial coordinates with q along x and r along y
+pub const HEX: [Neighbor; 6] = [
+    Neighbor::Top,
+    Neighbor::TopRight,
+    Neighbor::Left,
+    Neighbor::Right,
+    Neighbor::BottomLeft,
+    Neighbor::Bottom,
+];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Neighborhood {
+    Orthogonal,
+    Moore,
+    Hex,
+}
+
+impl Neighborhood {
+    pub fn directions(&self) -> &'static [Neighbor] {
+        match self {
+            Neighborhood::Orthogonal => &ORTHOGONAL,
+            Neighborhood::Moore => &NEIGHBORS,
+            Neighborhood::Hex => &HEX,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, cells: Vec<T>) -> Option<Self> {
+        let height = match width {
+            0 if cells.is_empty() => 0,
+            0 => return None,
+            _ => cells.len() / width,
+        };
+        match width * height == cells.len() {
+            true => Some(Self {
+                width,
+                height,
+                cells,
+            }),
+            false => None,
+        }
+    }
+
+    pub fn from_rows<R: IntoIterator<Item = T>>(rows: impl IntoIterator<Item = R>) -> Option<Self> {
+        let mut width = None;
+        let mut cells = Vec::new();
+        for row in rows {
+            let before = cells.len();
+            cells.extend(row);
+            if *width.get_or_insert(cells.len() - before) != cells.len() - before {
+                return None;
+            }
+        }
+        Self::new(width.unwrap_or_default(), cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: &Point<usize>) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    fn index(&self, point: &Point<usize>) -> Option<usize> {
+        match self.contains(point) {
+            true => Some(point.y * self.width + point.x),
+            false => None,
+        }
+    }
+
+    pub fn get(&self, point: &Point<usize>) -> Option<&T> {
+        self.index(point).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: &Point<usize>) -> Option<&mut T> {
+        self.index(point).map(move |index| &mut self.cells[index])
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point<usize>> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        match y < self.height {
+            true => Some(&self.cells[y * self.width..(y + 1) * self.width]),
+            false => None,
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).flat_map(move |y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        match x < self.width {
+            true => Some(self.cells.iter().skip(x).step_by(self.width)),
+            false => None,
+        }
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).flat_map(move |x| self.column(x))
+    }
+
+    pub fn neighbors(&self, point: &Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        self.neighbors_in(point, Neighborhood::Moore)
+    }
+
+    pub fn neighbors_in(
+        &self,
+        point: &Point<usize>,
+        neighborhood: Neighborhood,
+    ) -> impl Iterator<Item = Point<usize>> + '_ {
+        let point = *point;
+        neighborhood
+            .directions()
+            .iter()
+            .flat_map(move |neighbor| neighbor.point(&point, 1))
+            .filter(move |neighbor| self.contains(neighbor))
+    }
+
+    pub fn ray(
+        &self,
+        from: &Point<usize>,
+        direction: Neighbor,
+    ) -> impl Iterator<Item = Point<usize>> + '_ {
+        let from = *from;
+        (1..).map_while(move |distance| {
+            direction
+                .point(&from, distance)
+                .filter(|point| self.contains(point))
+        })
+    }
+
+    pub fn raycast<P>(
+        &self,
+        from: &Point<usize>,
+        direction: Neighbor,
+        predicate: P,
+    ) -> Option<Point<usize>>
+    where
+        P: Fn(&T) -> bool,
+    {
+        self.ray(from, direction)
+            .find(|point| predicate(&self[*point]))
+    }
+
+    pub fn visibility<P>(&self, neighborhood: Neighborhood, predicate: P) -> Grid<Vec<Point<usize>>>
+    where
+        P: Fn(&T) -> bool,
+    {
+        self.map(|point, _| {
+            neighborhood
+                .directions()
+                .iter()
+                .flat_map(|direction| self.raycast(&point, *direction, &predicate))
+                .collect()
+        })
+    }
+
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+    where
+        F: FnMut(Point<usize>, &T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.iter().map(|(point, value)| f(point, value)).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<usize>) -> &Self::Output {
+        self.get(&point)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", point))
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<usize>) -> &mut Self::Output {
+        self.get_mut(&point)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", point))
+    }
+}
+
+type Cells<T> = Vec<(Point<usize>, Option<T>)>;
+
+fn cells<T: MaybeFrom<char>>(input: &str, empty: Option<char>) -> Result<Cells<T>, ParseError> {
+    let mut cells = Vec::new();
+    let mut width = None;
+    let lines = input.lines().collect_vec();
+    let rows = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(0, |last| last + 1);
+    for (y, line) in lines[..rows].iter().enumerate() {
+        if line.trim().is_empty() {
+            return Err(ParseError::new(format!("row {} is empty", y)).on_line(y + 1));
+        }
+        for (x, c) in line.chars().enumerate() {
+            let cell = match T::maybe_from(c) {
+                Some(cell) => Some(cell),
+                None if Some(c) == empty => None,
+                None => {
+                    return Err(ParseError::at(
+                        x + 1,
+                        format!("unknown cell '{}' at ({}, {})", c, x, y),
+                    )
+                    .on_line(y + 1))
+                }
+            };
+            cells.push((Point::new(x, y), cell));
+        }
+        let length = line.chars().count();
+        if *width.get_or_insert(length) != length {
+            return Err(ParseError::new(format!(
+                "row {} has {} cells, expected {}",
+                y,
+                length,
+                width.unwrap_or_default()
+            ))
+            .on_line(y + 1));
+        }
+    }
+    Ok(cells)
+}
+
+fn grid_of<T>(cells: Vec<(Point<usize>, T)>) -> Grid<T> {
+    let width = cells
+        .iter()
+        .map(|(point, _)| point.x + 1)
+        .max()
+        .unwrap_or_default();
+    Grid::new(width, cells.into_iter().map(|(_, cell)| cell).collect())
+        .expect("rows have equal widths")
+}
+
+pub fn parse_grid<T: MaybeFrom<char>>(input: &str) -> Result<Grid<T>, ParseError> {
+    let cells = cells(input, None)?;
+    Ok(grid_of(
+        cells
+            .into_iter()
+            .flat_map(|(point, cell)| cell.map(|cell| (point, cell)))
+            .collect(),
+    ))
+}
+
+pub fn parse_grid_with_empty<T: MaybeFrom<char>>(
+    input: &str,
+    empty: char,
+) -> Result<Grid<Option<T>>, ParseError> {
+    cells(input, Some(empty)).map(grid_of)
+}
+
+pub fn parse_points<T: MaybeFrom<char>>(
+    input: &str,
+    empty: char,
+) -> Result<HashMap<Point<usize>, T>, ParseError> {
+    Ok(cells(input, Some(empty))?
+        .into_iter()
+        .flat_map(|(point, cell)| cell.map(|cell| (point, cell)))
+        .collect())
+}
+
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default)]
 pub struct Vector<T> {
     pub x: T,
@@ -258,6 +565,7 @@ where
 mod tests {
     use super::*;
     use parameterized::parameterized;
+    use std::iter::FromIterator;
 
     #[parameterized(cases = {
         (-90, Some(Vector::new(-4, 10))),
@@ -282,4 +590,157 @@ mod tests {
             base.rotate(cases.0)
         );
     }
+
+    fn grid() -> Grid<u8> {
+        Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap()
+    }
+
+    #[test]
+    fn grid_shape() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 6);
+        assert_eq!(grid.get(&Point::new(3, 0)), None);
+        assert_eq!(grid.get(&Point::new(0, 2)), None);
+        assert_eq!(grid.rows().collect_vec(), vec![&[1, 2, 3], &[4, 5, 6]]);
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.copied().collect_vec())
+                .collect_vec(),
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]]
+        );
+        assert!(grid.column(3).is_none());
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), None);
+        assert_eq!(Grid::<u8>::new(2, vec![1, 2, 3]), None);
+        assert_eq!(
+            Grid::<u8>::from_rows(Vec::<Vec<u8>>::new()).map(|grid| grid.height()),
+            Some(0)
+        );
+    }
+
+    #[parameterized(case = {
+        (Point::new(0, 0), vec![2, 4, 5]),
+        (Point::new(1, 1), vec![1, 2, 3, 4, 6]),
+        (Point::new(2, 0), vec![2, 5, 6]),
+    })]
+    fn grid_neighbors(case: (Point<usize>, Vec<u8>)) {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbors(&case.0)
+                .map(|point| grid[point])
+                .sorted()
+                .collect_vec(),
+            case.1
+        );
+    }
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, MaybeFrom)]
+    enum Cell {
+        #[token('#')]
+        Wall,
+        #[token('o')]
+        Ball,
+    }
+
+    #[test]
+    fn parse_text() {
+        use Cell::*;
+        assert_eq!(
+            parse_grid("#o\no#\n\n\n"),
+            Ok(Grid::from_rows(vec![vec![Wall, Ball], vec![Ball, Wall]]).unwrap())
+        );
+        assert_eq!(
+            parse_grid_with_empty("#.\n.o\n", '.'),
+            Ok(Grid::from_rows(vec![vec![Some(Wall), None], vec![None, Some(Ball)]]).unwrap())
+        );
+        assert_eq!(
+            parse_points("#.\n.o\n", '.'),
+            Ok(HashMap::from_iter(vec![
+                (Point::new(0, 0), Wall),
+                (Point::new(1, 1), Ball)
+            ]))
+        );
+    }
+
+    #[parameterized(case = {
+        ("#.\n.o\n", "line 1, column 2: unknown cell '.' at (1, 0)"),
+        ("##\n#o#\n", "line 2: row 1 has 3 cells, expected 2"),
+        ("##\n#x\n", "line 2, column 2: unknown cell 'x' at (1, 1)"),
+        ("##\n\n#o\n", "line 2: row 1 is empty"),
+        ("\n##\n", "line 1: row 0 is empty"),
+    })]
+    fn invalid_text(case: (&str, &str)) {
+        assert_eq!(
+            parse_grid::<Cell>(case.0).map_err(|error| error.to_string()),
+            Err(case.1.to_string())
+        );
+    }
+
+    #[parameterized(case = {
+        (Neighborhood::Orthogonal, Point::new(1, 1), 4, vec![2, 4, 6]),
+        (Neighborhood::Orthogonal, Point::new(0, 0), 2, vec![2, 4]),
+        (Neighborhood::Moore, Point::new(1, 1), 8, vec![1, 2, 3, 4, 6]),
+        (Neighborhood::Hex, Point::new(1, 1), 6, vec![2, 3, 4, 6]),
+        (Neighborhood::Hex, Point::new(0, 0), 2, vec![2, 4]),
+    })]
+    fn neighborhoods(case: (Neighborhood, Point<usize>, usize, Vec<u8>)) {
+        let grid = grid();
+        assert_eq!(case.1.neighbors_in(case.0).count(), case.2);
+        assert_eq!(
+            grid.neighbors_in(&case.1, case.0)
+                .map(|point| grid[point])
+                .sorted()
+                .collect_vec(),
+            case.3
+        );
+    }
+
+    #[test]
+    fn hex_neighbors_are_adjacent() {
+        let origin = Point::new(0, 0);
+        assert!(origin
+            .neighbors_in(Neighborhood::Hex)
+            .all(|neighbor| origin.hex_distance(&neighbor) == 1));
+        assert_eq!(origin.hex_distance(&Point::new(3, -1)), 3);
+        assert_eq!(origin.hex_distance(&Point::new(-2, -2)), 4);
+    }
+
+    fn room() -> Grid<char> {
+        Grid::from_rows("#...\n.o..\n..#.\n#..#".lines().map(|line| line.chars())).unwrap()
+    }
+
+    type Cast = (Neighbor, Vec<(usize, usize)>, Option<(usize, usize)>);
+
+    #[parameterized(case = {
+        (Neighbor::Right, vec![(2, 1), (3, 1)], None),
+        (Neighbor::BottomRight, vec![(2, 2), (3, 3)], Some((2, 2))),
+        (Neighbor::TopLeft, vec![(0, 0)], Some((0, 0))),
+        (Neighbor::BottomLeft, vec![(0, 2)], None),
+    })]
+    fn raycast(case: Cast) {
+        let grid = room();
+        let from = Point::new(1, 1);
+        assert_eq!(
+            grid.ray(&from, case.0).collect_vec(),
+            case.1.iter().map(Point::from).collect_vec()
+        );
+        assert_eq!(
+            grid.raycast(&from, case.0, |tile| *tile == '#'),
+            case.2.map(Point::from)
+        );
+    }
+
+    #[test]
+    fn visibility() {
+        let grid = room();
+        let visible = grid.visibility(Neighborhood::Moore, |tile| *tile != '.');
+        assert_eq!(
+            visible[Point::new(1, 1)].iter().sorted().collect_vec(),
+            vec![&Point::new(0, 0), &Point::new(2, 2)]
+        );
+        assert_eq!(
+            visible[Point::new(0, 3)].iter().sorted().collect_vec(),
+            vec![&Point::new(0, 0), &Point::new(3, 3)]
+        );
+    }
 }
@@ -1,10 +1,5 @@
 use num::{One, Zero};
 use std::ops::AddAssign;
-use std::str::FromStr;
-
-pub fn str_to_ints<F: FromStr>(s: &str) -> Vec<F> {
-    s.lines().flat_map(|l| l.parse::<F>()).collect()
-}
 
 pub struct Counter<T> {
     value: T,
@@ -18,6 +13,12 @@ impl<T: One + Zero + Copy> Counter<T> {
     }
 }
 
+impl<T: One + Zero + Copy> Default for Counter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: One + Zero + Copy + AddAssign> Iterator for Counter<T> {
     type Item = T;
 
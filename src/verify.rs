@@ -0,0 +1,244 @@
+use crate::input::{self, Source};
+use crate::parse::ParseError;
+use crate::pool;
+use crate::registry::Day;
+use crate::solution::Answer;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), Answer>,
+}
+
+impl Answers {
+    pub fn default_path() -> PathBuf {
+        input::data_dir().join("answers")
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|error| format!("failed to read {}: {}", path.display(), error))?;
+        Self::parse(&content).map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    pub fn parse(content: &str) -> Result<Self, ParseError> {
+        let mut answers = BTreeMap::new();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid =
+                || ParseError::new(format!("invalid entry '{}'", line)).on_line(index + 1);
+            let (day, rest) = line.split_once(' ').ok_or_else(invalid)?;
+            let (part, answer) = rest.trim_start().split_once(' ').ok_or_else(invalid)?;
+            let day = day.parse::<u8>().map_err(|_| invalid())?;
+            let part = part.parse::<u8>().map_err(|_| invalid())?;
+            let answer = answer.trim().parse().unwrap_or_else(|never| match never {});
+            if answers.insert((day, part), answer).is_some() {
+                return Err(ParseError::new(format!(
+                    "duplicate answer for day {} part {}",
+                    day, part
+                ))
+                .on_line(index + 1));
+            }
+        }
+        Ok(Self { answers })
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Outcome {
+    Pass,
+    Fail { expected: Answer, actual: Answer },
+    Unrecorded(Answer),
+    Error(String),
+}
+
+impl Outcome {
+    pub fn is_pass(&self) -> bool {
+        matches!(self, Outcome::Pass)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "ok"),
+            Outcome::Fail { expected, actual } => {
+                write!(f, "FAIL  expected {}, got {}", expected, actual)
+            }
+            Outcome::Unrecorded(actual) => write!(f, "FAIL  no answer recorded, got {}", actual),
+            Outcome::Error(error) => write!(f, "FAIL  {}", error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub outcome: Outcome,
+}
+
+pub fn compare(expected: Option<&Answer>, actual: Answer) -> Outcome {
+    match expected {
+        Some(expected) if *expected == actual => Outcome::Pass,
+        Some(expected) => Outcome::Fail {
+            expected: expected.clone(),
+            actual,
+        },
+        None => Outcome::Unrecorded(actual),
+    }
+}
+
+fn guarded<T>(f: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|payload| Err(format!("panicked: {}", pool::panic_message(payload))))
+}
+
+fn check_input(day: &Day, input: Result<String, String>, answers: &Answers) -> Vec<Check> {
+    let parsed = input
+        .and_then(|input| guarded(|| day.solver.parse(&input).map_err(|error| error.to_string())));
+    day.parts()
+        .map(|part| Check {
+            day: day.number,
+            part,
+            outcome: match &parsed {
+                Ok(parsed) => match guarded(|| Ok(parsed.solve(part))) {
+                    Ok(Some(actual)) => compare(answers.get(day.number, part), actual),
+                    Ok(None) => Outcome::Error(format!("part {} did not produce an answer", part)),
+                    Err(error) => Outcome::Error(error),
+                },
+                Err(error) => Outcome::Error(error.clone()),
+            },
+        })
+        .collect()
+}
+
+pub fn check_day(day: &Day, answers: &Answers) -> Vec<Check> {
+    let input = input::load(day.number, &Source::Default).map_err(|error| error.to_string());
+    check_input(day, input, answers)
+}
+
+pub fn report(checks: &[Check]) -> String {
+    let mut report = String::from("day  part  result\n");
+    for check in checks {
+        report += &format!("{:>3}  {:>4}  {}\n", check.day, check.part, check.outcome);
+    }
+    let passed = checks
+        .iter()
+        .filter(|check| check.outcome.is_pass())
+        .count();
+    report += &format!("{} passed, {} failed", passed, checks.len() - passed);
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Solution;
+    use parameterized::parameterized;
+
+    struct Fragile;
+
+    impl Solution for Fragile {
+        type Input = i64;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            match input {
+                "panic" => panic!("unparsable"),
+                input => input.parse().map_err(|_| ParseError::new(input)),
+            }
+        }
+
+        fn part1(input: &Self::Input) -> Answer {
+            (*input).into()
+        }
+
+        fn part2(input: &Self::Input) -> Option<Answer> {
+            panic!("no part 2 for {}", input)
+        }
+    }
+
+    const FRAGILE: Day = Day {
+        number: 1,
+        solver: &Fragile,
+    };
+
+    #[test]
+    fn parse_answers() {
+        let answers = Answers::parse("# comment\n1 1 32064\n\n1 2 abc def\n").unwrap();
+        assert_eq!(answers.get(1, 1), Some(&Answer::Int(32064)));
+        assert_eq!(
+            answers.get(1, 2),
+            Some(&Answer::Text("abc def".to_string()))
+        );
+        assert_eq!(answers.get(2, 1), None);
+    }
+
+    #[parameterized(case = {
+        ("1 1", "line 1: invalid entry '1 1'"),
+        ("x 1 5", "line 1: invalid entry 'x 1 5'"),
+        ("1 1 5\n1 1 6", "line 2: duplicate answer for day 1 part 1"),
+    })]
+    fn invalid_answers(case: (&str, &str)) {
+        assert_eq!(
+            Answers::parse(case.0).map_err(|error| error.to_string()),
+            Err(case.1.to_string())
+        );
+    }
+
+    #[parameterized(case = {
+        (Some(Answer::Int(5)), Answer::Int(5), Outcome::Pass),
+        (Some(Answer::Int(5)), Answer::Int(6), Outcome::Fail { expected: Answer::Int(5), actual: Answer::Int(6) }),
+        (None, Answer::Int(6), Outcome::Unrecorded(Answer::Int(6))),
+    })]
+    fn compare_answers(case: (Option<Answer>, Answer, Outcome)) {
+        assert_eq!(compare(case.0.as_ref(), case.1), case.2);
+    }
+
+    #[test]
+    fn report_table() {
+        let checks = [
+            Check {
+                day: 1,
+                part: 1,
+                outcome: Outcome::Pass,
+            },
+            Check {
+                day: 12,
+                part: 2,
+                outcome: Outcome::Fail {
+                    expected: Answer::Int(5),
+                    actual: Answer::Int(6),
+                },
+            },
+        ];
+        assert_eq!(
+            report(&checks),
+            "day  part  result\n  1     1  ok\n 12     2  FAIL  expected 5, got 6\n1 passed, 1 failed"
+        );
+    }
+
+    #[parameterized(case = {
+        ("5", vec![Outcome::Pass, Outcome::Error("panicked: no part 2 for 5".to_string())]),
+        ("panic", vec![Outcome::Error("panicked: unparsable".to_string()); 2]),
+    })]
+    fn isolates_panics(case: (&str, Vec<Outcome>)) {
+        let answers = Answers::parse("1 1 5").unwrap();
+        let outcomes = check_input(&FRAGILE, Ok(case.0.to_string()), &answers)
+            .into_iter()
+            .map(|check| check.outcome)
+            .collect::<Vec<_>>();
+        assert_eq!(outcomes, case.1);
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum Change {
+    Unchanged,
+    Modified(String),
+    Failed(io::Error),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Seen {
+    Nothing,
+    Content(u64),
+    Error(io::ErrorKind),
+}
+
+pub struct Watcher {
+    path: PathBuf,
+    seen: Seen,
+}
+
+impl Watcher {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            seen: Seen::Nothing,
+        }
+    }
+
+    pub fn poll(&mut self) -> Change {
+        match fs::read_to_string(&self.path) {
+            Ok(content) => {
+                let mut hasher = DefaultHasher::new();
+                content.hash(&mut hasher);
+                let seen = Seen::Content(hasher.finish());
+                if seen == self.seen {
+                    Change::Unchanged
+                } else {
+                    self.seen = seen;
+                    Change::Modified(content)
+                }
+            }
+            Err(error) => {
+                let seen = Seen::Error(error.kind());
+                if seen == self.seen {
+                    Change::Unchanged
+                } else {
+                    self.seen = seen;
+                    Change::Failed(error)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn poll() {
+        let path = env::temp_dir().join(format!("aoc2020-watch-{}", std::process::id()));
+        fs::remove_file(&path).ok();
+        let mut watcher = Watcher::new(path.clone());
+        assert!(matches!(watcher.poll(), Change::Failed(_)));
+        assert!(matches!(watcher.poll(), Change::Unchanged));
+        fs::write(&path, "1\n2\n").unwrap();
+        assert!(matches!(watcher.poll(), Change::Modified(content) if content == "1\n2\n"));
+        assert!(matches!(watcher.poll(), Change::Unchanged));
+        fs::write(&path, "1\n2\n").unwrap();
+        assert!(matches!(watcher.poll(), Change::Unchanged));
+        fs::write(&path, "3\n").unwrap();
+        assert!(matches!(watcher.poll(), Change::Modified(content) if content == "3\n"));
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(watcher.poll(), Change::Failed(_)));
+    }
+}
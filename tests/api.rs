@@ -0,0 +1,40 @@
+use aoc2020::day1::Day1;
+use aoc2020::input::{self, Source};
+use aoc2020::registry;
+use aoc2020::solution::{Answer, Solution};
+use aoc2020::twod::{Point, Vector};
+use aoc2020::verify::{self, Answers};
+
+#[test]
+fn typed_solution() {
+    let input = Day1::parse("1721\n979\n366\n299\n675\n1456").expect("failed to parse");
+    assert_eq!(Day1::part1(&input), Answer::Int(514579));
+    assert_eq!(Day1::part2(&input), Some(Answer::Int(241861950)));
+}
+
+#[test]
+fn registry_solver() {
+    let day = registry::find(2).expect("day 2 missing");
+    let parsed = day
+        .solver
+        .parse(&input::load(2, &Source::Default).expect("failed to load input"))
+        .expect("failed to parse");
+    let answers = Answers::load(&Answers::default_path()).expect("failed to load answers");
+    for part in day.parts() {
+        assert_eq!(parsed.solve(part).as_ref(), answers.get(2, part));
+    }
+}
+
+#[test]
+fn verify_day() {
+    let answers = Answers::load(&Answers::default_path()).expect("failed to load answers");
+    let checks = verify::check_day(registry::find(12).expect("day 12 missing"), &answers);
+    assert!(checks.iter().all(|check| check.outcome.is_pass()));
+}
+
+#[test]
+fn twod_reuse() {
+    let point = Point::new(1, 2) + Vector::new(3, -4) * 2;
+    assert_eq!(point, Point::new(7, -6));
+    assert_eq!(point.manhattan_distance(&Point::default()), 13);
+}
@@ -0,0 +1,18 @@
+use aoc2020::maybe_from::MaybeFrom;
+use aoc2020::render::Render;
+
+#[derive(Debug, Eq, PartialEq, MaybeFrom)]
+#[maybe_from(crate = "aoc2020")]
+enum Cell {
+    #[token('.')]
+    Open,
+    #[token('#')]
+    Wall,
+}
+
+#[test]
+fn derive_outside_the_crate() {
+    assert_eq!(Cell::maybe_from('#'), Some(Cell::Wall));
+    assert_eq!(Cell::maybe_from('x'), None);
+    assert_eq!(Cell::Open.render(), ".");
+}
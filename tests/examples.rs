@@ -0,0 +1,19 @@
+use aoc2020::examples;
+use aoc2020::registry::DAYS;
+
+#[test]
+fn puzzle_examples() {
+    let mut checks = Vec::new();
+    for day in DAYS {
+        for example in examples::load(day.number).expect("failed to load examples") {
+            checks.extend(examples::check(day, &example));
+        }
+    }
+    let failures = checks
+        .iter()
+        .filter(|check| !check.outcome.is_pass())
+        .map(|check| check.to_string())
+        .collect::<Vec<_>>();
+    assert!(!checks.is_empty(), "no examples found");
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}